@@ -0,0 +1,87 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+/// Crates every day is built on, a change to them could change any day's answers.
+const SHARED_CRATES: &[&str] = &["aoc_common", "geometry"];
+
+/// Hashes the sources of every day crate along with the shared crates they use, so the answer
+/// cache can tell when a solver changed without anyone having to bump its version.
+fn main() {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let workspace = Path::new(&manifest_dir).join("..");
+
+    // The runner's manifest gains a dependency whenever a day is added
+    println!("cargo:rerun-if-changed=Cargo.toml");
+
+    let mut shared = vec![];
+    for name in SHARED_CRATES {
+        shared.extend(crate_sources(&workspace.join(name)));
+    }
+
+    let mut days: Vec<(u8, PathBuf)> = fs::read_dir(&workspace)
+        .expect("the workspace is readable")
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let day = path.file_name()?.to_str()?.strip_prefix("day_")?.parse().ok()?;
+
+            path.join("Cargo.toml").exists().then_some((day, path))
+        })
+        .collect();
+    days.sort();
+
+    let mut hashes = String::new();
+
+    for (day, dir) in days {
+        let mut hasher = Sha256::new();
+
+        for path in crate_sources(&dir).iter().chain(&shared) {
+            let relative = path.strip_prefix(&workspace).unwrap_or(path);
+
+            hasher.update(relative.to_string_lossy().as_bytes());
+            hasher.update(fs::read(path).expect("the source files are readable"));
+        }
+
+        let hash: String = hasher.finalize().iter().map(|b| format!("{b:02x}")).collect();
+        writeln!(hashes, "    ({day}, \"{}\"),", &hash[..16]).unwrap();
+    }
+
+    let out = PathBuf::from(std::env::var("OUT_DIR").unwrap()).join("source_hashes.rs");
+    let contents = format!("pub const SOURCE_HASHES: &[(u8, &str)] = &[\n{hashes}];\n");
+
+    fs::write(out, contents).expect("the build output directory is writable");
+}
+
+/// The manifest and every file under `src` of the crate, sorted so they hash the same way on
+/// every build.
+fn crate_sources(dir: &Path) -> Vec<PathBuf> {
+    let manifest = dir.join("Cargo.toml");
+    let src = dir.join("src");
+
+    println!("cargo:rerun-if-changed={}", manifest.display());
+    println!("cargo:rerun-if-changed={}", src.display());
+
+    let mut files = vec![manifest];
+    let mut dirs = vec![src];
+
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+
+            if path.is_dir() {
+                dirs.push(path);
+            } else {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    files
+}
@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use aoc_common::Part;
+use serde::Deserialize;
+
+/// Default location of the checked in answers, at the root of the workspace.
+pub const ANSWERS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../answers.toml");
+
+/// The known correct answers for each day, keyed first by the day (`day_01`) then by the name of
+/// the input file in that day's data directory.
+#[derive(Debug, Default, Deserialize)]
+#[serde(transparent)]
+pub struct Answers(BTreeMap<String, BTreeMap<String, ExpectedAnswers>>);
+
+impl Answers {
+    pub fn expected(&self, day: u8, input_name: &str, part: Part) -> Option<&str> {
+        let expected = self.0.get(&format!("day_{day:02}"))?.get(input_name)?;
+
+        match part {
+            Part::One => expected.part_one.as_deref(),
+            Part::Two => expected.part_two.as_deref(),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Answers, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| format!("unable to read answers file {}: {err}", path.display()))?;
+
+        toml::from_str(&contents)
+            .map_err(|err| format!("invalid answers file {}: {err}", path.display()))
+    }
+
+    pub fn verify(&self, day: u8, input_name: &str, part: Part, answer: Option<&str>) -> Verdict {
+        let Some(expected) = self.expected(day, input_name, part) else {
+            return Verdict::Unknown;
+        };
+
+        // Rendered answers are stored as multi-line strings which pick up a trailing newline
+        if Some(expected.trim_end()) == answer.map(str::trim_end) {
+            Verdict::Pass
+        } else {
+            Verdict::Fail(expected.to_string())
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ExpectedAnswers {
+    part_one: Option<String>,
+    part_two: Option<String>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Verdict {
+    Pass,
+    /// The answer didn't match the contained expected answer.
+    Fail(String),
+    Unknown,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_ANSWERS: &str = r#"
+[day_01.input]
+part_one = "66487"
+
+[day_10.sample]
+part_two = """
+##..
+###.
+"""
+"#;
+
+    #[test]
+    fn test_verdicts() {
+        let answers: Answers = toml::from_str(SAMPLE_ANSWERS).unwrap();
+
+        assert_eq!(answers.verify(1, "input", Part::One, Some("66487")), Verdict::Pass);
+        assert_eq!(
+            answers.verify(1, "input", Part::One, Some("66486")),
+            Verdict::Fail("66487".to_string())
+        );
+        assert_eq!(
+            answers.verify(1, "input", Part::One, None),
+            Verdict::Fail("66487".to_string())
+        );
+
+        assert_eq!(answers.verify(1, "input", Part::Two, Some("1")), Verdict::Unknown);
+        assert_eq!(answers.verify(1, "sample", Part::One, Some("1")), Verdict::Unknown);
+        assert_eq!(answers.verify(2, "input", Part::One, Some("1")), Verdict::Unknown);
+    }
+
+    #[test]
+    fn test_multiline_answers() {
+        let answers: Answers = toml::from_str(SAMPLE_ANSWERS).unwrap();
+
+        let rendered = "##..\n###.";
+        assert_eq!(answers.verify(10, "sample", Part::Two, Some(rendered)), Verdict::Pass);
+    }
+
+    #[test]
+    fn test_checked_in_answers_parse() {
+        let answers = Answers::load(Path::new(ANSWERS_PATH)).unwrap();
+        assert_eq!(answers.expected(14, "sample", Part::Two), Some("93"));
+    }
+}
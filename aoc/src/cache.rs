@@ -0,0 +1,153 @@
+use std::path::PathBuf;
+
+use aoc_common::{content_hash, Params, Part};
+use serde::{Deserialize, Serialize};
+
+/// Default location of the cached answers, at the root of the workspace.
+pub const CACHE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../.cache/answers");
+
+// Hashes of every day's sources, generated by the build script
+include!(concat!(env!("OUT_DIR"), "/source_hashes.rs"));
+
+/// Answers from previous runs, so solving a day again on the same input returns them instantly.
+/// Each answer is keyed by everything that could change it: the input bytes, the version of the
+/// day's solver and the puzzle parameters. Changing any of them misses the cache and solves the
+/// part again. Parts without an answer, or that were interrupted, are never cached.
+pub struct AnswerCache {
+    pub dir: PathBuf,
+}
+
+/// The key identifying a single part solved by a particular version of a day on an input.
+pub struct CacheKey {
+    day: u8,
+    part: Part,
+    hash: String,
+
+    version: String,
+    input_hash: String,
+    params: serde_json::Value,
+}
+
+/// A cached answer along with what it was computed from, stored as JSON.
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct CachedAnswer {
+    key: String,
+    version: String,
+    input_hash: String,
+    params: serde_json::Value,
+    answer: String,
+}
+
+impl CacheKey {
+    pub fn new(day: u8, version: &str, part: Part, data: &[u8], params: &Params) -> CacheKey {
+        let input_hash = content_hash(data);
+        let params = serde_json::to_value(params).unwrap();
+
+        // The parameters are kept in a sorted map so the same values always serialize the same
+        let hash = content_hash(
+            format!("{day}\n{part}\n{version}\n{input_hash}\n{params}").as_bytes(),
+        );
+
+        CacheKey {
+            day,
+            part,
+            hash,
+
+            version: version.to_string(),
+            input_hash,
+            params,
+        }
+    }
+}
+
+/// The version of a day's solver answers are cached for. This is the crate's version along with a
+/// hash of the sources it's built from, the day's own and those of the crates shared by every
+/// day, so any change to them invalidates its answers without needing a version bump.
+pub fn solver_version(day: u8, crate_version: &str) -> String {
+    match SOURCE_HASHES.iter().find(|(number, _)| *number == day) {
+        Some((_, hash)) => format!("{crate_version}+{hash}"),
+        None => crate_version.to_string(),
+    }
+}
+
+impl AnswerCache {
+    /// The answer cached for the key, if there is one. Entries that can't be read are treated as
+    /// missing so the part is solved again and the entry replaced.
+    pub fn get(&self, key: &CacheKey) -> Option<String> {
+        let contents = std::fs::read(self.path(key)).ok()?;
+        let cached: CachedAnswer = serde_json::from_slice(&contents).ok()?;
+
+        (cached.key == key.hash).then_some(cached.answer)
+    }
+
+    /// Caches the answer for the key, failing to do so only loses the time it would have saved
+    /// so it's logged rather than reported.
+    pub fn insert(&self, key: &CacheKey, answer: &str) {
+        let cached = CachedAnswer {
+            key: key.hash.clone(),
+            version: key.version.clone(),
+            input_hash: key.input_hash.clone(),
+            params: key.params.clone(),
+            answer: answer.to_string(),
+        };
+
+        let path = self.path(key);
+        let result = std::fs::create_dir_all(&self.dir)
+            .and_then(|_| std::fs::write(&path, serde_json::to_vec_pretty(&cached).unwrap()));
+
+        if let Err(err) = result {
+            tracing::warn!(path = %path.display(), %err, "failed to cache answer");
+        }
+    }
+
+    fn path(&self, key: &CacheKey) -> PathBuf {
+        let file_name = format!("day_{:02}-part_{}-{}.json", key.day, key.part, &key.hash[..16]);
+
+        self.dir.join(file_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cached_answers() {
+        let dir = std::env::temp_dir().join(format!("aoc-cache-test-{}", std::process::id()));
+        let cache = AnswerCache { dir: dir.clone() };
+
+        let data = b"498,4 -> 498,6 -> 496,6\n";
+        let params = Params::default();
+        let key = CacheKey::new(14, "0.1.0", Part::One, data, &params);
+
+        assert_eq!(cache.get(&key), None);
+
+        cache.insert(&key, "24");
+        assert_eq!(cache.get(&key).as_deref(), Some("24"));
+
+        // Changing anything the answer depends on misses the cache
+        let other_params = Params::new([("width".to_string(), 40.into())]);
+        let misses = [
+            CacheKey::new(14, "0.1.0", Part::Two, data, &params),
+            CacheKey::new(14, "0.2.0", Part::One, data, &params),
+            CacheKey::new(14, "0.1.0", Part::One, b"498,4 -> 498,7\n", &params),
+            CacheKey::new(14, "0.1.0", Part::One, data, &other_params),
+        ];
+
+        for key in &misses {
+            assert_eq!(cache.get(key), None);
+        }
+
+        // Every day's sources are hashed into its version
+        let version = solver_version(14, "0.1.0");
+        assert!(version.starts_with("0.1.0+"));
+        assert_ne!(version, solver_version(15, "0.1.0"));
+        assert_eq!(solver_version(24, "0.1.0"), "0.1.0");
+
+        // A corrupted entry is ignored
+        std::fs::write(cache.path(&key), "{").unwrap();
+        assert_eq!(cache.get(&key), None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
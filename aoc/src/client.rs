@@ -0,0 +1,163 @@
+use std::io::Read;
+use std::time::Duration;
+
+use aoc_common::Part;
+
+use crate::config::ClientConfig;
+
+/// Identifies the runner to the Advent of Code servers, as their automation guidelines ask.
+const USER_AGENT: &str = concat!("aoc-runner/", env!("CARGO_PKG_VERSION"), " (Advent of Code 2022 solutions)");
+
+/// Minimal client for the parts of the Advent of Code website the runner uses.
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    year: u16,
+    session: String,
+}
+
+impl Client {
+    pub fn new(config: &ClientConfig) -> Result<Client, String> {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Ok(Client {
+            agent,
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            year: config.year,
+            session: config.session()?,
+        })
+    }
+
+    /// Downloads the puzzle input for a day.
+    pub fn input(&self, day: u8) -> Result<Vec<u8>, String> {
+        let url = format!("{}/{}/day/{day}/input", self.base_url, self.year);
+
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|err| describe_error(&url, err))?;
+
+        let mut data = vec![];
+        response
+            .into_reader()
+            .read_to_end(&mut data)
+            .map_err(|err| format!("failed to read the response from {url}: {err}"))?;
+
+        Ok(data)
+    }
+
+    /// Submits an answer for one part of a day, returning the text of the page the site responds
+    /// with. Working out what the response means is left to the caller.
+    pub fn submit(&self, day: u8, part: Part, answer: &str) -> Result<String, String> {
+        let url = format!("{}/{}/day/{day}/answer", self.base_url, self.year);
+        let level = part.number().to_string();
+
+        self.agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &level), ("answer", answer)])
+            .map_err(|err| describe_error(&url, err))?
+            .into_string()
+            .map_err(|err| format!("failed to read the response from {url}: {err}"))
+    }
+}
+
+fn describe_error(url: &str, err: ureq::Error) -> String {
+    match err {
+        ureq::Error::Status(code, response) => {
+            let body = response.into_string().unwrap_or_default();
+            let reason = body.lines().next().unwrap_or("").trim();
+
+            match code {
+                400 | 500 => format!("{url} rejected the request ({code}), is the session token still valid? {reason}"),
+                404 => format!("{url} wasn't found, the puzzle may not be unlocked yet. {reason}"),
+                _ => format!("{url} responded with {code}: {reason}"),
+            }
+        }
+        ureq::Error::Transport(transport) => format!("unable to reach the site, {transport}"),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    use std::thread::JoinHandle;
+
+    /// Serves canned responses to the given number of requests on a local port, standing in for
+    /// the Advent of Code site. The handler receives the method, url, cookie and body of each
+    /// request and returns the status and body to respond with.
+    pub fn mock_server(
+        requests: usize,
+        handler: impl Fn(&str, &str, &str, &str) -> (u16, String) + Send + 'static,
+    ) -> (ClientConfig, JoinHandle<()>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", server.server_addr().to_ip().unwrap());
+
+        let handle = std::thread::spawn(move || {
+            for mut request in server.incoming_requests().take(requests) {
+                let cookie = request
+                    .headers()
+                    .iter()
+                    .find(|h| h.field.equiv("Cookie"))
+                    .map(|h| h.value.to_string())
+                    .unwrap_or_default();
+
+                let mut body = String::new();
+                request.as_reader().read_to_string(&mut body).unwrap();
+
+                let (status, response) = handler(request.method().as_str(), request.url(), &cookie, &body);
+                request
+                    .respond(tiny_http::Response::from_string(response).with_status_code(status))
+                    .unwrap();
+            }
+        });
+
+        let config = ClientConfig {
+            base_url,
+            year: 2022,
+            session: Some("53616c7465645f5f".to_string()),
+        };
+
+        (config, handle)
+    }
+
+    #[test]
+    fn test_fetch_input() {
+        let (config, server) = mock_server(1, |method, url, cookie, _| {
+            assert_eq!(method, "GET");
+            assert_eq!(url, "/2022/day/1/input");
+            assert_eq!(cookie, "session=53616c7465645f5f");
+
+            (200, "1000\n2000\n".to_string())
+        });
+
+        let client = Client::new(&config).unwrap();
+        assert_eq!(client.input(1).unwrap(), b"1000\n2000\n");
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_fetch_errors() {
+        let (config, server) = mock_server(2, |_, url, _, _| match url {
+            "/2022/day/24/input" => (404, "Please don't repeatedly request this endpoint before it unlocks!".to_string()),
+            _ => (400, "Puzzle inputs differ by user.  Please log in to get your puzzle input.".to_string()),
+        });
+
+        let client = Client::new(&config).unwrap();
+
+        let err = client.input(24).unwrap_err();
+        assert!(err.contains("may not be unlocked yet"), "{err}");
+
+        let err = client.input(1).unwrap_err();
+        assert!(err.contains("is the session token still valid?"), "{err}");
+
+        server.join().unwrap();
+    }
+}
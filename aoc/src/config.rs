@@ -0,0 +1,99 @@
+use std::path::Path;
+
+use serde::Deserialize;
+
+/// Default location of the runner's configuration, at the root of the workspace.
+pub const CONFIG_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../aoc.toml");
+
+/// Environment variable that takes precedence over the session token in the config file, so the
+/// token doesn't have to be written anywhere it could be committed.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+/// Settings read from `aoc.toml`. Every setting has a default so the file is optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub client: ClientConfig,
+}
+
+/// How to reach the Advent of Code website.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClientConfig {
+    /// The site to talk to, pointing this at a local server allows testing without touching the
+    /// real site.
+    pub base_url: String,
+    pub year: u16,
+    /// Value of the `session` cookie from a logged in browser.
+    pub session: Option<String>,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        ClientConfig {
+            base_url: "https://adventofcode.com".to_string(),
+            year: 2022,
+            session: None,
+        }
+    }
+}
+
+impl ClientConfig {
+    /// The session token from the environment, falling back to the config file.
+    pub fn session(&self) -> Result<String, String> {
+        std::env::var(SESSION_ENV)
+            .ok()
+            .or_else(|| self.session.clone())
+            .map(|session| session.trim().to_string())
+            .filter(|session| !session.is_empty())
+            .ok_or_else(|| {
+                format!("no session token, set {SESSION_ENV} or `session` under [client] in aoc.toml")
+            })
+    }
+}
+
+impl Config {
+    /// Reads the config file, a missing file gives the default config.
+    pub fn load(path: &Path) -> Result<Config, String> {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Config::default()),
+            Err(err) => return Err(format!("unable to read config file {}: {err}", path.display())),
+        };
+
+        toml::from_str(&contents)
+            .map_err(|err| format!("invalid config file {}: {err}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.client.base_url, "https://adventofcode.com");
+        assert_eq!(config.client.year, 2022);
+
+        let config: Config = toml::from_str(
+            r#"
+            [client]
+            base_url = "http://127.0.0.1:8080"
+            session = "53616c7465645f5f"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.client.base_url, "http://127.0.0.1:8080");
+        assert_eq!(config.client.year, 2022);
+        assert_eq!(config.client.session.as_deref(), Some("53616c7465645f5f"));
+
+        assert!(toml::from_str::<Config>("[client]\nsesion = \"typo\"\n").is_err());
+    }
+
+    #[test]
+    fn test_missing_config_file() {
+        let config = Config::load(Path::new("/nonexistent/aoc.toml")).unwrap();
+        assert_eq!(config.client.session, None);
+    }
+}
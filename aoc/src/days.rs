@@ -0,0 +1,349 @@
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use aoc_common::generate::InputRng;
+use aoc_common::{
+    content_hash, normalize, Checkpoint, Interrupted, Normalization, ParseError, Params, Part,
+    Progress, Simulation, Solution,
+};
+
+use crate::cache::{solver_version, AnswerCache, CacheKey};
+
+/// Every day with a working solution, in calendar order.
+pub const DAYS: &[Day] = &[
+    Day::new::<day_01::Day01>(),
+    Day::new::<day_02::Day02>(),
+    Day::new::<day_10::Day10>(),
+    Day::new::<day_14::Day14>(),
+    Day::new::<day_15::Day15>(),
+    Day::new::<day_25::Day25>(),
+];
+
+/// Default location of the checkpoints long running solvers save their progress into.
+pub const CHECKPOINT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../.checkpoints");
+
+type SolutionRunner = fn(
+    &[u8],
+    &[Part],
+    &Progress,
+    Option<&Checkpoints>,
+    Option<&AnswerCache>,
+) -> Result<DayReport, ParseError>;
+
+type InputGenerator = fn(&mut InputRng, usize) -> Option<String>;
+
+type SimulationBuilder = fn(&[u8], Part) -> Result<Option<Box<dyn Simulation>>, ParseError>;
+
+type ParamsChecker = fn(&Params) -> Result<(), String>;
+
+/// Type erased handle to a single day's [`Solution`] so they can all be dispatched from one place.
+pub struct Day {
+    pub number: u8,
+    pub data_dir: &'static str,
+
+    runner: SolutionRunner,
+    generator: InputGenerator,
+    simulator: SimulationBuilder,
+    params_checker: ParamsChecker,
+}
+
+impl Day {
+    /// Checks the parameters are ones the day takes, with values of the right type. See
+    /// [`Solution::Params`].
+    pub fn check_params(&self, params: &Params) -> Result<(), String> {
+        (self.params_checker)(params)
+    }
+
+    pub fn find(number: u8) -> Option<&'static Day> {
+        DAYS.iter().find(|d| d.number == number)
+    }
+
+    const fn new<S: Solution>() -> Self {
+        Day {
+            number: S::DAY,
+            data_dir: S::DATA_DIR,
+
+            runner: run_solution::<S>,
+            generator: S::generate,
+            simulator: simulate_solution::<S>,
+            params_checker: check_solution_params::<S>,
+        }
+    }
+
+    /// A random input for the day with roughly `size` entries, see [`Solution::generate`].
+    pub fn generate(&self, rng: &mut InputRng, size: usize) -> Option<String> {
+        (self.generator)(rng, size)
+    }
+
+    /// Every input file in the day's data directory by name, sorted so reports are stable.
+    pub fn inputs(&self) -> std::io::Result<Vec<(String, PathBuf)>> {
+        let mut inputs = vec![];
+
+        for entry in std::fs::read_dir(self.data_dir)? {
+            let entry = entry?;
+
+            if entry.file_type()?.is_file() {
+                inputs.push((entry.file_name().to_string_lossy().to_string(), entry.path()));
+            }
+        }
+
+        inputs.sort();
+
+        Ok(inputs)
+    }
+
+    /// Parses the data and solves the requested parts. Each part is solved with the provided
+    /// progress handle installed as the current one so long running solvers can report progress
+    /// and be interrupted. When checkpoints are enabled each part also gets its own checkpoint to
+    /// resume from.
+    ///
+    /// With a cache, parts answered before on the same input with the same parameters and version
+    /// of the day take their answer from the cache instead of being solved. The input is only
+    /// parsed when a part needs solving.
+    pub fn run(
+        &self,
+        data: &[u8],
+        parts: &[Part],
+        progress: &Progress,
+        checkpoints: Option<&Checkpoints>,
+        cache: Option<&AnswerCache>,
+    ) -> Result<DayReport, ParseError> {
+        (self.runner)(data, parts, progress, checkpoints, cache)
+    }
+
+    /// Parses the data and sets up the simulation solving the part, see [`Solution::simulate`].
+    pub fn simulate(
+        &self,
+        data: &[u8],
+        part: Part,
+    ) -> Result<Option<Box<dyn Simulation>>, ParseError> {
+        (self.simulator)(data, part)
+    }
+}
+
+/// Where checkpoints are kept and how often they are saved.
+pub struct Checkpoints {
+    pub dir: PathBuf,
+    pub interval: Duration,
+}
+
+impl Checkpoints {
+    /// The checkpoint for one part of a day. The file name includes a hash of the input and the
+    /// puzzle parameters so state saved while solving one input, or with other parameters, is
+    /// never resumed for another.
+    pub fn for_part(&self, day: u8, part: Part, data: &[u8], params: &Params) -> Checkpoint {
+        let params = serde_json::to_string(params).unwrap();
+        let hash = content_hash(format!("{}\n{params}", content_hash(data)).as_bytes());
+        let file_name = format!("day_{day:02}-part_{part}-{}.json", &hash[..16]);
+
+        Checkpoint::new(self.dir.join(file_name), self.interval)
+    }
+}
+
+#[derive(Debug)]
+pub struct DayReport {
+    /// Changes made to the input before it was parsed.
+    pub normalizations: Vec<Normalization>,
+    pub parse_time: Duration,
+    pub parts: Vec<PartReport>,
+}
+
+#[derive(Debug)]
+pub struct PartReport {
+    pub part: Part,
+    pub answer: Option<String>,
+    pub elapsed: Duration,
+
+    /// Set when the part was stopped before it could finish.
+    pub interrupted: Option<Interrupted>,
+    /// Whether the answer came from the cache rather than solving the part.
+    pub cached: bool,
+}
+
+fn run_solution<S: Solution>(
+    data: &[u8],
+    parts: &[Part],
+    progress: &Progress,
+    checkpoints: Option<&Checkpoints>,
+    cache: Option<&AnswerCache>,
+) -> Result<DayReport, ParseError> {
+    let normalized = normalize(data);
+
+    let params = Params::current();
+    let cached: Vec<(CacheKey, Option<String>)> = match cache {
+        Some(cache) => parts
+            .iter()
+            .map(|&part| {
+                let version = solver_version(S::DAY, S::VERSION);
+                let key = CacheKey::new(S::DAY, &version, part, data, &params);
+                let answer = cache.get(&key);
+
+                (key, answer)
+            })
+            .collect(),
+        None => vec![],
+    };
+
+    let all_cached = !cached.is_empty() && cached.iter().all(|(_, answer)| answer.is_some());
+
+    let start = Instant::now();
+    let input = if all_cached { None } else { Some(S::parse(&normalized.data)?) };
+    let parse_time = start.elapsed();
+
+    let parts = parts
+        .iter()
+        .enumerate()
+        .map(|(idx, &part)| {
+            let (cache_key, cached_answer) = match cached.get(idx) {
+                Some((key, answer)) => (Some(key), answer.clone()),
+                None => (None, None),
+            };
+
+            if cached_answer.is_some() {
+                return PartReport {
+                    part,
+                    answer: cached_answer,
+                    elapsed: Duration::ZERO,
+
+                    interrupted: None,
+                    cached: true,
+                };
+            }
+
+            let input = input.as_ref().expect("the input is parsed when any part isn't cached");
+
+            let checkpoint = checkpoints.map(|c| c.for_part(S::DAY, part, data, &params));
+
+            if let Some(checkpoint) = checkpoint.as_ref().filter(|c| c.exists()) {
+                eprintln!(
+                    "Day {:02} part {part}: resuming from {}",
+                    S::DAY,
+                    checkpoint.path().display()
+                );
+            }
+
+            let start = Instant::now();
+            let answer = progress.scope(|| match &checkpoint {
+                Some(checkpoint) => checkpoint.scope(|| S::solve(input, part)),
+                None => S::solve(input, part),
+            });
+
+            let elapsed = start.elapsed();
+            let interrupted = progress.interruption();
+
+            if let (Some(cache), Some(key), Some(answer), None) =
+                (cache, cache_key, &answer, &interrupted)
+            {
+                cache.insert(key, answer);
+            }
+
+            PartReport {
+                part,
+                answer,
+                elapsed,
+
+                interrupted,
+                cached: false,
+            }
+        })
+        .collect();
+
+    Ok(DayReport {
+        normalizations: normalized.changes,
+        parse_time,
+        parts,
+    })
+}
+
+fn check_solution_params<S: Solution>(params: &Params) -> Result<(), String> {
+    params.parse::<S::Params>().map(drop)
+}
+
+fn simulate_solution<S: Solution>(
+    data: &[u8],
+    part: Part,
+) -> Result<Option<Box<dyn Simulation>>, ParseError> {
+    let input = S::parse(&normalize(data).data)?;
+
+    Ok(S::simulate(&input, part))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_params() {
+        let params = |values: &[(&str, i64)]| {
+            Params::new(values.iter().map(|&(k, v)| (k.to_string(), v.into())))
+        };
+
+        let day_10 = Day::find(10).unwrap();
+        assert_eq!(day_10.check_params(&params(&[("display_width", 20)])), Ok(()));
+
+        let err = day_10.check_params(&params(&[("display_width", 0)])).unwrap_err();
+        assert!(err.contains("the display must be at least one pixel wide and high, not 0x6"));
+
+        let day_14 = Day::find(14).unwrap();
+        let err = day_14.check_params(&params(&[("spawner_x", 0)])).unwrap_err();
+        assert!(err.contains("the spawner at 0,0 is outside of the simulated area"));
+
+        let day_01 = Day::find(1).unwrap();
+        assert!(day_01.check_params(&params(&[("row", 10)])).is_err());
+    }
+
+    #[test]
+    fn test_checkpoints_keyed_by_params() {
+        let dir = std::env::temp_dir().join(format!("aoc-checkpoints-test-{}", std::process::id()));
+        let checkpoints = Checkpoints { dir: dir.clone(), interval: Duration::from_secs(3600) };
+
+        let sample = include_str!("../../day_14/data/sample");
+        let wide = Params::new([("width".to_string(), 800.into())]);
+
+        // Sand left over from a run with the default parameters, away from the sample's rocks
+        let checkpoint = checkpoints.for_part(14, Part::One, sample.as_bytes(), &Params::default());
+        checkpoint.save(&serde_json::json!({ "has_floor": false, "resting_sand": [[300, 5]] }));
+
+        let day = Day::find(14).unwrap();
+        let run = |params: &Params| {
+            let report = params.scope(|| {
+                day.run(sample.as_bytes(), &[Part::One], &Progress::new(), Some(&checkpoints), None)
+            });
+
+            report.unwrap().parts[0].answer.clone()
+        };
+
+        // Other parameters don't resume the stale sand, the same parameters do
+        assert_eq!(run(&wide).as_deref(), Some("24"));
+        assert!(checkpoint.exists());
+        assert_eq!(run(&Params::default()).as_deref(), Some("25"));
+        assert!(!checkpoint.exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_inputs_from_other_machines() {
+        let sample = include_str!("../../day_14/data/sample");
+        let edited = format!("\u{feff}{}", sample.replace('\n', " \r\n"));
+
+        let day = Day::find(14).unwrap();
+        let clean = day.run(sample.as_bytes(), &Part::ALL, &Progress::new(), None, None).unwrap();
+        let report = day.run(edited.as_bytes(), &Part::ALL, &Progress::new(), None, None).unwrap();
+
+        let answers = |report: &DayReport| -> Vec<Option<String>> {
+            report.parts.iter().map(|p| p.answer.clone()).collect()
+        };
+        assert_eq!(answers(&report), answers(&clean));
+
+        let lines = sample.lines().count();
+        assert_eq!(
+            report.normalizations,
+            vec![
+                Normalization::ByteOrderMark,
+                Normalization::CrlfLineEndings { lines },
+                Normalization::TrailingWhitespace { lines },
+            ]
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use crate::client::Client;
+use crate::config::Config;
+use crate::days::Day;
+
+/// Root of the workspace, where the day crates live.
+pub const WORKSPACE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Fetched {
+    /// The input was already in the day's data directory so nothing was downloaded.
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Downloads a day's puzzle input into its data directory, unless it's already there.
+pub fn fetch(day: u8, config: &Config) -> ExitCode {
+    if !(1..=25).contains(&day) {
+        eprintln!("there is no day {day} in the advent calendar");
+        return ExitCode::FAILURE;
+    }
+
+    let path = input_path(day);
+
+    let result = Client::new(&config.client).and_then(|client| fetch_into(&client, day, &path));
+
+    match result {
+        Ok(Fetched::Cached(path)) => {
+            println!("Day {day:02} input is already at {}", path.display());
+            ExitCode::SUCCESS
+        }
+        Ok(Fetched::Downloaded(path)) => {
+            println!("Day {day:02} input saved to {}", path.display());
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("failed to fetch the input for day {day}: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Where a day's input is kept, days without a solution yet still follow the `day_NN/data`
+/// layout.
+pub fn input_path(day: u8) -> PathBuf {
+    let data_dir = match Day::find(day) {
+        Some(day) => PathBuf::from(day.data_dir),
+        None => Path::new(WORKSPACE_DIR).join(format!("day_{day:02}")).join("data"),
+    };
+
+    data_dir.join("input")
+}
+
+/// Inputs never change once a puzzle is unlocked so an existing file is never downloaded again,
+/// this keeps the load on the Advent of Code servers to the minimum. Empty files are placeholders
+/// left by `aoc new` and are replaced.
+fn fetch_into(client: &Client, day: u8, path: &Path) -> Result<Fetched, String> {
+    if path.metadata().is_ok_and(|m| m.len() > 0) {
+        return Ok(Fetched::Cached(path.to_path_buf()));
+    }
+
+    let data = client.input(day)?;
+
+    let write = || -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        // Written next to the destination first so a failed write never leaves a partial input
+        // behind that would be mistaken for a cached one.
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, &data)?;
+        std::fs::rename(&tmp_path, path)
+    };
+
+    write().map_err(|err| format!("unable to save the input to {}: {err}", path.display()))?;
+
+    Ok(Fetched::Downloaded(path.to_path_buf()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::client::tests::mock_server;
+
+    #[test]
+    fn test_fetch_is_cached() {
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-test-{}", std::process::id()));
+        let path = dir.join("day_03").join("data").join("input");
+
+        // The server only answers a single request, a second download would fail
+        let (config, server) = mock_server(1, |_, url, _, _| {
+            assert_eq!(url, "/2022/day/3/input");
+            (200, "vJrwpWtwJgWrhcsFMMfFFhFp\n".to_string())
+        });
+        let client = Client::new(&config).unwrap();
+
+        assert_eq!(fetch_into(&client, 3, &path), Ok(Fetched::Downloaded(path.clone())));
+        assert_eq!(fetch_into(&client, 3, &path), Ok(Fetched::Cached(path.clone())));
+        assert_eq!(std::fs::read(&path).unwrap(), b"vJrwpWtwJgWrhcsFMMfFFhFp\n");
+
+        server.join().unwrap();
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_placeholder_is_replaced() {
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-placeholder-test-{}", std::process::id()));
+        let path = dir.join("input");
+
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(&path, "").unwrap();
+
+        let (config, server) = mock_server(1, |_, _, _, _| (200, "30373\n25512\n".to_string()));
+        let client = Client::new(&config).unwrap();
+
+        assert_eq!(fetch_into(&client, 8, &path), Ok(Fetched::Downloaded(path.clone())));
+        assert_eq!(std::fs::read(&path).unwrap(), b"30373\n25512\n");
+
+        server.join().unwrap();
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_input_paths() {
+        assert!(input_path(14).ends_with("day_14/data/input"));
+        assert!(input_path(11).ends_with("day_11/data/input"));
+    }
+}
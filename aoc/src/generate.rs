@@ -0,0 +1,84 @@
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use aoc_common::generate::seeded;
+
+use crate::days::Day;
+use crate::params::{ParamOverride, ParamsFile};
+
+pub struct GenerateOptions<'a> {
+    pub size: usize,
+    /// Picked from the clock when not given, it's always reported so the input can be generated
+    /// again.
+    pub seed: Option<u64>,
+    /// Where to write the input, by default it's printed.
+    pub output: Option<&'a Path>,
+
+    pub params: &'a ParamsFile,
+    pub param_overrides: &'a [ParamOverride],
+}
+
+/// Generates a random input for a day, to stress test or benchmark its solution with inputs of
+/// any size.
+pub fn generate(day: u8, options: GenerateOptions) -> ExitCode {
+    let Some(day) = Day::find(day) else {
+        eprintln!("day {day} doesn't have a solution yet");
+        return ExitCode::FAILURE;
+    };
+
+    let seed = options.seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64)
+    });
+
+    // The generated input isn't one of the day's files, so only the parameters shared by all of
+    // its inputs apply
+    let params = match options.params.for_day(day, None, options.param_overrides) {
+        Ok(params) => params,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let Some(input) = params.scope(|| day.generate(&mut seeded(seed), options.size)) else {
+        eprintln!("day {} doesn't have an input generator", day.number);
+        return ExitCode::FAILURE;
+    };
+
+    eprintln!("Day {:02}: generated {} entries with seed {seed}", day.number, options.size);
+
+    match options.output {
+        Some(path) => {
+            if let Err(err) = std::fs::write(path, input) {
+                eprintln!("unable to write {}: {err}", path.display());
+                return ExitCode::FAILURE;
+            }
+        }
+        None => print!("{input}"),
+    }
+
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::days::DAYS;
+    use aoc_common::{Part, Progress};
+
+    #[test]
+    fn test_generated_inputs_solve() {
+        for day in DAYS {
+            let input = day.generate(&mut seeded(2022), 5).unwrap();
+            let report = day
+                .run(input.as_bytes(), &[Part::One], &Progress::default(), None, None)
+                .unwrap_or_else(|err| panic!("day {} generated an invalid input: {err}", day.number));
+
+            assert!(report.normalizations.is_empty());
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use std::fs::File;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Layer;
+
+pub struct LogOptions {
+    /// How many times `--verbose` was given, each one shows a more detailed level of events.
+    pub verbosity: u8,
+    /// Only show errors.
+    pub quiet: bool,
+    /// Also write the events to this file as one JSON object per line.
+    pub file: Option<PathBuf>,
+    /// Print the events to stderr, turned off while something else owns the terminal.
+    pub console: bool,
+}
+
+impl LogOptions {
+    /// Warnings are shown by default, every `--verbose` adds the next level down to trace.
+    fn level(&self) -> LevelFilter {
+        if self.quiet {
+            return LevelFilter::ERROR;
+        }
+
+        match self.verbosity {
+            0 => LevelFilter::WARN,
+            1 => LevelFilter::INFO,
+            2 => LevelFilter::DEBUG,
+            _ => LevelFilter::TRACE,
+        }
+    }
+}
+
+/// Installs the subscriber receiving the events logged by the solutions and the runner. Events
+/// go to stderr so they never mix with the answers on stdout.
+pub fn init(options: &LogOptions) -> Result<(), String> {
+    let level = options.level();
+
+    let console = options.console.then(|| {
+        tracing_subscriber::fmt::layer()
+            .with_writer(std::io::stderr)
+            .with_ansi(std::io::stderr().is_terminal())
+            .with_filter(level)
+    });
+
+    let file = match &options.file {
+        Some(path) => {
+            let file = File::create(path)
+                .map_err(|err| format!("unable to create log file {}: {err}", path.display()))?;
+
+            let layer = tracing_subscriber::fmt::layer()
+                .json()
+                .with_writer(Mutex::new(file))
+                .with_filter(level);
+
+            Some(layer)
+        }
+        None => None,
+    };
+
+    tracing_subscriber::registry()
+        .with(console)
+        .with(file)
+        .try_init()
+        .map_err(|err| format!("unable to set up logging: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(verbosity: u8, quiet: bool) -> LogOptions {
+        LogOptions {
+            verbosity,
+            quiet,
+            file: None,
+            console: true,
+        }
+    }
+
+    #[test]
+    fn test_verbosity_levels() {
+        assert_eq!(options(0, false).level(), LevelFilter::WARN);
+        assert_eq!(options(1, false).level(), LevelFilter::INFO);
+        assert_eq!(options(2, false).level(), LevelFilter::DEBUG);
+        assert_eq!(options(5, false).level(), LevelFilter::TRACE);
+        assert_eq!(options(0, true).level(), LevelFilter::ERROR);
+    }
+}
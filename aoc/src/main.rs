@@ -0,0 +1,460 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use aoc_common::Part;
+use clap::{ArgAction, Parser, Subcommand};
+
+mod answers;
+mod cache;
+mod client;
+mod config;
+mod days;
+mod fetch;
+mod generate;
+mod logging;
+mod output;
+mod params;
+mod record;
+mod run;
+mod run_all;
+mod scaffold;
+mod stepper;
+mod submit;
+mod timing;
+mod verify;
+
+use cache::AnswerCache;
+use config::Config;
+use generate::GenerateOptions;
+use logging::LogOptions;
+use params::{ParamOverride, ParamsFile};
+use record::{RecordFormat, RecordOptions};
+use run::{Format, RunOptions};
+use run_all::RunAllOptions;
+use stepper::StepOptions;
+use submit::SubmitOptions;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Runs the Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Configuration file for the runner
+    #[arg(long, global = true, default_value = config::CONFIG_PATH)]
+    config: PathBuf,
+
+    /// File with the parameters of each day's puzzle, such as the row day 15 looks at
+    #[arg(long, global = true, default_value = params::PARAMS_PATH)]
+    params: PathBuf,
+
+    /// Show more of what the solutions are doing, repeat for more detail (-v info, -vv debug,
+    /// -vvv trace)
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    verbose: u8,
+
+    /// Only show errors logged by the solutions
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Also write everything logged to this file, one JSON object per line
+    #[arg(long, global = true, value_name = "PATH")]
+    log_file: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a single day's puzzle, or every day at once
+    Run {
+        /// The day of the advent calendar to solve
+        #[arg(required_unless_present = "all")]
+        day: Option<u8>,
+
+        /// Solve every day in parallel from their bundled inputs and print a summary of them all
+        #[arg(
+            long,
+            conflicts_with_all = [
+                "day", "input", "time", "checkpoint", "record", "progress", "param"
+            ]
+        )]
+        all: bool,
+
+        /// How many days to solve at the same time, by default one per CPU core
+        #[arg(long, requires = "all")]
+        jobs: Option<usize>,
+
+        /// Only solve one part of the puzzle (1 or 2), by default both parts are solved
+        #[arg(long, value_parser = parse_part)]
+        part: Option<Part>,
+
+        /// Path to the puzzle input, `-` reads it from stdin. Defaults to the input in the day's
+        /// data directory.
+        #[arg(long)]
+        input: Option<String>,
+
+        /// Set a parameter of the puzzle, taking precedence over the parameters file. Can be
+        /// repeated.
+        #[arg(long, value_name = "KEY=VALUE", value_parser = params::parse_param)]
+        param: Vec<ParamOverride>,
+
+        /// Report how long parsing and each part took, repeating the solution to get stable
+        /// numbers
+        #[arg(long)]
+        time: bool,
+
+        /// How many times to repeat the solution when timing it
+        #[arg(long, default_value_t = 10, requires = "time")]
+        runs: usize,
+
+        /// Output format of the answers and timing report, `json` prints a single line record
+        /// of the run for scripts to consume
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+
+        /// Periodically report the progress of long running solvers, every this many seconds
+        #[arg(long, value_name = "SECONDS", num_args = 0..=1, default_missing_value = "5")]
+        progress: Option<f64>,
+
+        /// Stop any part that is still running after this many seconds, reporting how far it
+        /// got
+        #[arg(long, value_name = "SECONDS")]
+        timeout: Option<f64>,
+
+        /// Report changes made to the input before it was parsed, such as removing a byte order
+        /// mark or converting CRLF line endings
+        #[arg(long)]
+        report_normalization: bool,
+
+        /// Save the progress of long running solvers every this many seconds, and resume from a
+        /// saved checkpoint for the same input if there is one
+        #[arg(
+            long,
+            value_name = "SECONDS",
+            num_args = 0..=1,
+            default_missing_value = "60",
+            conflicts_with = "time"
+        )]
+        checkpoint: Option<f64>,
+
+        /// Directory the checkpoints are kept in
+        #[arg(long, default_value = days::CHECKPOINT_DIR, requires = "checkpoint")]
+        checkpoint_dir: PathBuf,
+
+        /// Record the simulation while solving the part and save the frames to this path, a GIF
+        /// file or a directory of numbered images depending on the format
+        #[arg(long, value_name = "PATH", requires = "part", conflicts_with = "time")]
+        record: Option<PathBuf>,
+
+        /// Capture a frame every this many steps of the simulation
+        #[arg(long, value_name = "STEPS", default_value_t = 1, requires = "record")]
+        record_every: usize,
+
+        /// Stop capturing steps after this many frames, the final state of the simulation is
+        /// always the last frame
+        #[arg(long, value_name = "FRAMES", default_value_t = 1000, requires = "record")]
+        record_limit: usize,
+
+        /// Format the recorded frames are saved in
+        #[arg(long, value_enum, default_value_t = RecordFormat::Gif, requires = "record")]
+        record_format: RecordFormat,
+
+        /// Size in pixels of each cell of the simulation in the recorded frames
+        #[arg(long, value_name = "PIXELS", default_value_t = 4, requires = "record")]
+        record_scale: usize,
+
+        /// Solve the puzzle even if the answer is cached from a previous run on the same input.
+        /// Timed and recorded runs always solve it
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Directory the cached answers are kept in
+        #[arg(long, default_value = cache::CACHE_DIR, conflicts_with = "no_cache")]
+        cache_dir: PathBuf,
+    },
+
+    /// Step through the simulation solving one part of a day interactively in the terminal,
+    /// inspecting its state as it goes
+    Step {
+        /// The day of the advent calendar to simulate
+        day: u8,
+
+        /// Which part of the puzzle to simulate (1 or 2)
+        #[arg(long, value_parser = parse_part, default_value = "1")]
+        part: Part,
+
+        /// Path to the puzzle input, `-` reads it from stdin. Defaults to the input in the day's
+        /// data directory.
+        #[arg(long)]
+        input: Option<String>,
+
+        /// Set a parameter of the puzzle, taking precedence over the parameters file. Can be
+        /// repeated.
+        #[arg(long, value_name = "KEY=VALUE", value_parser = params::parse_param)]
+        param: Vec<ParamOverride>,
+    },
+
+    /// Check every day's answers against the known correct answers
+    Verify {
+        /// Only verify a single day, by default every day is verified
+        day: Option<u8>,
+
+        /// File containing the expected answers
+        #[arg(long, default_value = answers::ANSWERS_PATH)]
+        answers: PathBuf,
+    },
+
+    /// Download a day's puzzle input into its data directory, inputs that were already
+    /// downloaded are left alone
+    Fetch {
+        /// The day of the advent calendar to download the input for
+        day: u8,
+    },
+
+    /// Generate a random puzzle input for a day, to stress test or benchmark its solution
+    Generate {
+        /// The day of the advent calendar to generate an input for
+        day: u8,
+
+        /// Roughly how many entries (elves, rounds, instructions...) the input should have
+        #[arg(long, default_value_t = 1000)]
+        size: usize,
+
+        /// Seed for the random generator, the same seed always generates the same input
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// Write the input to this file instead of printing it
+        #[arg(long)]
+        output: Option<PathBuf>,
+
+        /// Set a parameter of the puzzle, taking precedence over the parameters file. Can be
+        /// repeated.
+        #[arg(long, value_name = "KEY=VALUE", value_parser = params::parse_param)]
+        param: Vec<ParamOverride>,
+    },
+
+    /// Create the crate for a new day from the template and add it to the workspace and runner
+    New {
+        /// The day of the advent calendar to create
+        day: u8,
+    },
+
+    /// Solve one part of a day and submit the answer, answers that are known to be wrong or
+    /// would be sent too soon after the last one are not submitted
+    Submit {
+        /// The day of the advent calendar the answer is for
+        day: u8,
+
+        /// Which part of the puzzle the answer is for (1 or 2)
+        #[arg(value_parser = parse_part)]
+        part: Part,
+
+        /// Submit this answer instead of solving the puzzle
+        #[arg(long)]
+        answer: Option<String>,
+
+        /// Path to the puzzle input, `-` reads it from stdin. Defaults to the input in the day's
+        /// data directory.
+        #[arg(long, conflicts_with = "answer")]
+        input: Option<String>,
+
+        /// Wait for the cooldown from a previous attempt to pass instead of giving up
+        #[arg(long)]
+        wait: bool,
+
+        /// File recording every submitted answer
+        #[arg(long, default_value = submit::HISTORY_PATH)]
+        history: PathBuf,
+    },
+}
+
+fn parse_part(value: &str) -> Result<Part, String> {
+    let number: u8 = value.parse().map_err(|_| format!("{value:?} isn't a part number"))?;
+    Part::try_from(number)
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let log_options = LogOptions {
+        verbosity: cli.verbose,
+        quiet: cli.quiet,
+        file: cli.log_file.clone(),
+        // The stepper draws over the whole terminal
+        console: !matches!(cli.command, Command::Step { .. }),
+    };
+
+    if let Err(err) = logging::init(&log_options) {
+        eprintln!("{err}");
+        return ExitCode::FAILURE;
+    }
+
+    // Only loaded by the commands that solve puzzles, so a broken file doesn't get in the way of
+    // the others
+    let load_params = || ParamsFile::load(&cli.params).inspect_err(|err| eprintln!("{err}"));
+
+    match cli.command {
+        Command::Run {
+            day,
+            all,
+            jobs,
+            part,
+            input,
+            param,
+            time,
+            runs,
+            format,
+            progress,
+            timeout,
+            report_normalization,
+            checkpoint,
+            checkpoint_dir,
+            record,
+            record_every,
+            record_limit,
+            record_format,
+            record_scale,
+            no_cache,
+            cache_dir,
+        } => {
+            let Ok(params) = load_params() else {
+                return ExitCode::FAILURE;
+            };
+
+            // Timing and recording are about solving the puzzle, not the answer
+            let cache = (!no_cache && !time && record.is_none())
+                .then_some(AnswerCache { dir: cache_dir });
+
+            let Some(day) = day.filter(|_| !all) else {
+                let options = RunAllOptions {
+                    part,
+                    format,
+                    timeout: timeout.map(Duration::from_secs_f64),
+                    jobs,
+                    params,
+                    cache,
+                };
+
+                return run_all::run_all(options);
+            };
+
+            let options = RunOptions {
+                part,
+                input: input.as_deref(),
+
+                params: &params,
+                param_overrides: &param,
+
+                timed_runs: time.then_some(runs),
+                format,
+
+                progress_interval: progress.map(Duration::from_secs_f64),
+                timeout: timeout.map(Duration::from_secs_f64),
+
+                report_normalization,
+
+                checkpoint_interval: checkpoint.map(Duration::from_secs_f64),
+                checkpoint_dir,
+
+                record: record.map(|path| RecordOptions {
+                    path,
+                    every: record_every,
+                    limit: record_limit,
+                    format: record_format,
+                    scale: record_scale,
+                }),
+
+                cache,
+            };
+
+            run::run(day, options)
+        }
+        Command::Step {
+            day,
+            part,
+            input,
+            param,
+        } => {
+            let Ok(params) = load_params() else {
+                return ExitCode::FAILURE;
+            };
+
+            let options = StepOptions {
+                part,
+                input: input.as_deref(),
+
+                params: &params,
+                param_overrides: &param,
+            };
+
+            stepper::step(day, options)
+        }
+        Command::Verify { day, answers } => match load_params() {
+            Ok(params) => verify::verify(day, &answers, &params),
+            Err(_) => ExitCode::FAILURE,
+        },
+        Command::Fetch { day } => match Config::load(&cli.config) {
+            Ok(config) => fetch::fetch(day, &config),
+            Err(err) => {
+                eprintln!("{err}");
+                ExitCode::FAILURE
+            }
+        },
+        Command::Generate {
+            day,
+            size,
+            seed,
+            output,
+            param,
+        } => {
+            let Ok(params) = load_params() else {
+                return ExitCode::FAILURE;
+            };
+
+            let options = GenerateOptions {
+                size,
+                seed,
+                output: output.as_deref(),
+
+                params: &params,
+                param_overrides: &param,
+            };
+
+            generate::generate(day, options)
+        }
+        Command::New { day } => scaffold::new_day(day),
+        Command::Submit {
+            day,
+            part,
+            answer,
+            input,
+            wait,
+            history,
+        } => {
+            let config = match Config::load(&cli.config) {
+                Ok(config) => config,
+                Err(err) => {
+                    eprintln!("{err}");
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            let Ok(params) = load_params() else {
+                return ExitCode::FAILURE;
+            };
+
+            let options = SubmitOptions {
+                day,
+                part,
+                answer,
+                input: input.as_deref(),
+                params: &params,
+                wait,
+                history_path: &history,
+            };
+
+            submit::submit(options, &config)
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::days::DayReport;
+use crate::timing::{as_nanos, TimingReport};
+
+/// Machine readable results of running a day, printed as a single line of JSON by
+/// `--format json` so scripts don't need to scrape the text output.
+#[derive(Debug, Serialize)]
+pub struct DayRecord {
+    pub day: u8,
+    /// Where the input was read from.
+    pub input: String,
+    /// SHA-256 of the input bytes, to tell which input the answers belong to.
+    pub input_hash: String,
+    /// Changes made to the input before it was parsed.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub normalizations: Vec<String>,
+    #[serde(rename = "parse_ns", serialize_with = "as_nanos")]
+    pub parse_time: Duration,
+    pub parts: Vec<PartRecord>,
+
+    /// Statistics over repeated runs, only present when timing was requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<TimingReport>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PartRecord {
+    pub part: u8,
+    pub answer: Option<String>,
+    /// The rows of answers that are rendered as a picture rather than a single value, such as
+    /// day 10's CRT.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display: Option<Vec<String>>,
+    #[serde(rename = "duration_ns", serialize_with = "as_nanos")]
+    pub duration: Duration,
+    /// Why the part was stopped before it finished, if it was.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interrupted: Option<String>,
+    /// Set when the answer came from the cache, the duration is then zero.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub cached: bool,
+}
+
+impl DayRecord {
+    pub fn new(day: u8, input: String, input_hash: String, report: &DayReport) -> DayRecord {
+        let parts = report
+            .parts
+            .iter()
+            .map(|part_report| {
+                let answer = part_report.answer.clone();
+                let display = answer
+                    .as_deref()
+                    .filter(|a| a.contains('\n'))
+                    .map(|a| a.lines().map(str::to_string).collect());
+
+                PartRecord {
+                    part: part_report.part.number(),
+                    answer,
+                    display,
+                    duration: part_report.elapsed,
+                    interrupted: part_report.interrupted.as_ref().map(|i| i.to_string()),
+                    cached: part_report.cached,
+                }
+            })
+            .collect();
+
+        DayRecord {
+            day,
+            input,
+            input_hash,
+            normalizations: report.normalizations.iter().map(|n| n.to_string()).collect(),
+            parse_time: report.parse_time,
+            parts,
+            timing: None,
+        }
+    }
+
+    pub fn with_timing(mut self, timing: TimingReport) -> DayRecord {
+        self.timing = Some(timing);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_common::Part;
+
+    use crate::days::PartReport;
+
+    #[test]
+    fn test_record_json() {
+        let report = DayReport {
+            normalizations: vec![],
+            parse_time: Duration::from_micros(5),
+            parts: vec![
+                PartReport {
+                    part: Part::One,
+                    answer: Some("13140".to_string()),
+                    elapsed: Duration::from_micros(2),
+
+                    interrupted: None,
+                    cached: false,
+                },
+                PartReport {
+                    part: Part::Two,
+                    answer: Some("##..\n#.#.".to_string()),
+                    elapsed: Duration::from_micros(3),
+
+                    interrupted: None,
+                    cached: false,
+                },
+            ],
+        };
+
+        let record = DayRecord::new(10, "data/sample".to_string(), "abc".to_string(), &report);
+        let json = serde_json::to_value(&record).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "day": 10,
+                "input": "data/sample",
+                "input_hash": "abc",
+                "parse_ns": 5000,
+                "parts": [
+                    { "part": 1, "answer": "13140", "duration_ns": 2000 },
+                    {
+                        "part": 2,
+                        "answer": "##..\n#.#.",
+                        "display": ["##..", "#.#."],
+                        "duration_ns": 3000,
+                    },
+                ],
+            })
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use aoc_common::{InputSource, Params};
+
+use crate::days::Day;
+
+/// Default location of the puzzle parameters, at the root of the workspace.
+pub const PARAMS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../params.toml");
+
+/// A parameter given on the command line, which takes precedence over the file.
+pub type ParamOverride = (String, toml::Value);
+
+/// Puzzle parameters read from `params.toml`. Each day has a `[day_NN]` table with the values
+/// for every input, and tables within it named after an input file with values for only that
+/// input:
+///
+/// ```toml
+/// [day_15]
+/// row = 2000000
+///
+/// [day_15.sample]
+/// row = 10
+/// ```
+///
+/// Parameters without a value anywhere keep the day's built in default.
+#[derive(Debug, Default)]
+pub struct ParamsFile {
+    days: BTreeMap<String, toml::Table>,
+}
+
+impl ParamsFile {
+    /// Reads the parameters file, a missing file leaves every day with its defaults.
+    pub fn load(path: &Path) -> Result<ParamsFile, String> {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(ParamsFile::default())
+            }
+            Err(err) => {
+                return Err(format!("unable to read parameters file {}: {err}", path.display()))
+            }
+        };
+
+        ParamsFile::parse(&contents)
+            .map_err(|err| format!("invalid parameters file {}: {err}", path.display()))
+    }
+
+    fn parse(contents: &str) -> Result<ParamsFile, String> {
+        let days: BTreeMap<String, toml::Table> =
+            toml::from_str(contents).map_err(|err| err.to_string())?;
+
+        if let Some(name) = days.keys().find(|name| day_number(name).is_none()) {
+            return Err(format!("[{name}] isn't a day, expected a table such as [day_01]"));
+        }
+
+        Ok(ParamsFile { days })
+    }
+
+    /// The parameters for solving the day's `input`, the name of a file in its data directory,
+    /// with the overrides applied on top. Fails when any of them aren't parameters the day takes
+    /// or have the wrong type.
+    pub fn for_day(
+        &self,
+        day: &Day,
+        input: Option<&str>,
+        overrides: &[ParamOverride],
+    ) -> Result<Params, String> {
+        let mut values = BTreeMap::new();
+
+        let table = self.days.iter().find(|(name, _)| day_number(name) == Some(day.number));
+
+        if let Some((_, table)) = table {
+            let shared = table.iter().filter(|(_, value)| !value.is_table());
+            values.extend(shared);
+
+            let per_input = input.and_then(|input| table.get(input)).and_then(|v| v.as_table());
+            values.extend(per_input.into_iter().flatten());
+        }
+
+        values.extend(overrides.iter().map(|(key, value)| (key, value)));
+
+        let values = values
+            .into_iter()
+            .map(|(key, value)| {
+                serde_json::to_value(value)
+                    .map(|value| (key.clone(), value))
+                    .map_err(|err| format!("unable to use parameter {key}: {err}"))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let params = Params::new(values);
+        day.check_params(&params)
+            .map_err(|err| format!("invalid parameters for day {:02}, {err}", day.number))?;
+
+        Ok(params)
+    }
+
+    /// The parameters for solving the day with the input read from `source`, inputs are matched
+    /// to the tables in the file by their file name.
+    pub fn for_source(
+        &self,
+        day: &Day,
+        source: &InputSource,
+        overrides: &[ParamOverride],
+    ) -> Result<Params, String> {
+        let input = match source {
+            InputSource::Bundled(path) | InputSource::File(path) => path.file_name(),
+            InputSource::Stdin => None,
+        };
+
+        self.for_day(day, input.and_then(|name| name.to_str()), overrides)
+    }
+}
+
+fn day_number(name: &str) -> Option<u8> {
+    name.strip_prefix("day_").and_then(|number| number.parse().ok())
+}
+
+/// Parses a `KEY=VALUE` parameter from the command line. The value is read as TOML so numbers
+/// and arrays have their usual types, anything else is taken as a string.
+pub fn parse_param(arg: &str) -> Result<ParamOverride, String> {
+    let (key, value) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("{arg:?} isn't a parameter, expected KEY=VALUE"))?;
+
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(format!("{arg:?} is missing the name of the parameter"));
+    }
+
+    let value = toml::from_str::<toml::Table>(&format!("value = {value}"))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(value.to_string()));
+
+    Ok((key.to_string(), value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: &str = r#"
+        [day_15]
+        row = 2000000
+
+        [day_15.sample]
+        row = 10
+        search_from = [0, 0]
+        search_to = [20, 20]
+
+        [day_14.sample]
+        width = 40
+    "#;
+
+    fn resolved(input: Option<&str>, overrides: &[ParamOverride]) -> Result<Params, String> {
+        let file = ParamsFile::parse(PARAMS).unwrap();
+        file.for_day(Day::find(15).unwrap(), input, overrides)
+    }
+
+    fn params(values: &[(&str, serde_json::Value)]) -> Params {
+        Params::new(values.iter().map(|(key, value)| (key.to_string(), value.clone())))
+    }
+
+    #[test]
+    fn test_resolve_params() {
+        use serde_json::json;
+
+        assert_eq!(resolved(Some("input"), &[]), Ok(params(&[("row", json!(2_000_000))])));
+        assert_eq!(resolved(None, &[]), Ok(params(&[("row", json!(2_000_000))])));
+
+        assert_eq!(
+            resolved(Some("sample"), &[parse_param("row=11").unwrap()]),
+            Ok(params(&[
+                ("row", json!(11)),
+                ("search_from", json!([0, 0])),
+                ("search_to", json!([20, 20])),
+            ]))
+        );
+
+        let err = resolved(Some("input"), &[parse_param("rows=11").unwrap()]).unwrap_err();
+        assert!(err.starts_with("invalid parameters for day 15, unknown field `rows`"));
+
+        let err = resolved(Some("input"), &[parse_param("row=ten").unwrap()]).unwrap_err();
+        assert!(err.starts_with("invalid parameters for day 15, invalid type"));
+
+        let file = ParamsFile::parse(PARAMS).unwrap();
+        assert!(file.for_day(Day::find(1).unwrap(), Some("sample"), &[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_params_file() {
+        assert!(ParamsFile::parse("").unwrap().days.is_empty());
+        assert!(ParamsFile::parse("[day_1x]\nrow = 1\n").is_err());
+        assert!(ParamsFile::parse("row = 1\n").is_err());
+
+        let file = ParamsFile::load(Path::new("/nonexistent/params.toml")).unwrap();
+        assert!(file.days.is_empty());
+    }
+
+    #[test]
+    fn test_parse_param() {
+        assert_eq!(parse_param("row=10"), Ok(("row".to_string(), toml::Value::Integer(10))));
+        assert_eq!(
+            parse_param("search_to=[20, 20]").map(|(_, value)| value.to_string()),
+            Ok("[20, 20]".to_string())
+        );
+        assert_eq!(
+            parse_param("name=sample"),
+            Ok(("name".to_string(), toml::Value::String("sample".to_string())))
+        );
+
+        assert!(parse_param("row").is_err());
+        assert!(parse_param("=10").is_err());
+    }
+}
@@ -0,0 +1,388 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use aoc_common::recorder::Rgb;
+use aoc_common::{Frame, FrameSink};
+use clap::ValueEnum;
+
+/// Delay between the frames of an animation, in hundredths of a second as GIFs measure it.
+const FRAME_DELAY: u16 = 5;
+
+/// Color of the parts of the canvas a frame doesn't cover, where the simulation hadn't reached
+/// yet.
+const BACKGROUND: Rgb = [0, 0, 0];
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum RecordFormat {
+    /// A single animated GIF
+    #[default]
+    Gif,
+    /// A directory of numbered PNG images
+    Png,
+    /// A directory of numbered PPM images
+    Ppm,
+}
+
+pub struct RecordOptions {
+    pub path: PathBuf,
+    /// Capture a frame every this many steps of the simulation.
+    pub every: usize,
+    /// Stop capturing steps after this many frames, keeping the last one for the final state.
+    pub limit: usize,
+    pub format: RecordFormat,
+    /// Width and height in pixels of every cell of the simulation.
+    pub scale: usize,
+}
+
+/// The area every frame is drawn on, so frames of a simulation are drawn at the same place in
+/// every image.
+#[derive(Debug, Eq, PartialEq)]
+struct Canvas {
+    left: isize,
+    top: isize,
+    width: usize,
+    height: usize,
+}
+
+impl Canvas {
+    fn of(frame: &Frame) -> Canvas {
+        Canvas {
+            left: frame.left,
+            top: frame.top,
+            width: frame.width,
+            height: frame.height,
+        }
+    }
+
+    /// Draws the frame at its place on the canvas, with every cell scaled up to a square of
+    /// `scale` pixels. Any part of the frame outside the canvas is left out.
+    fn render(&self, frame: &Frame, scale: usize) -> Vec<Rgb> {
+        let mut image = Vec::with_capacity(self.width * self.height * scale * scale);
+
+        for y in 0..self.height * scale {
+            let row = self.top + (y / scale) as isize - frame.top;
+
+            for x in 0..self.width * scale {
+                let column = self.left + (x / scale) as isize - frame.left;
+
+                let inside = (0..frame.width as isize).contains(&column)
+                    && (0..frame.height as isize).contains(&row);
+
+                image.push(if inside {
+                    frame.pixel(column as usize, row as usize)
+                } else {
+                    BACKGROUND
+                });
+            }
+        }
+
+        image
+    }
+}
+
+/// Writes the frames of a recording in the requested format as they're captured, so only the
+/// frame being written is kept in memory. The first frame sets the size of the images, which is
+/// why the simulations render the whole area they can cover in every frame.
+pub struct FrameWriter {
+    path: PathBuf,
+    format: RecordFormat,
+    scale: usize,
+
+    /// Set up by the first frame.
+    canvas: Option<Canvas>,
+    gif: Option<gif::Encoder<BufWriter<File>>>,
+    frames: usize,
+}
+
+impl FrameWriter {
+    pub fn new(options: &RecordOptions) -> Self {
+        FrameWriter {
+            path: options.path.clone(),
+            format: options.format,
+            scale: options.scale.max(1),
+
+            canvas: None,
+            gif: None,
+            frames: 0,
+        }
+    }
+
+    fn error(&self, err: impl std::fmt::Display) -> String {
+        format!("unable to save the recording to {}: {err}", self.path.display())
+    }
+
+    fn write(&mut self, frame: &Frame) -> Result<(), String> {
+        if self.canvas.is_none() {
+            let canvas = Canvas::of(frame);
+
+            match self.format {
+                RecordFormat::Gif => self.gif = Some(create_gif(&self.path, &canvas, self.scale)?),
+                RecordFormat::Png | RecordFormat::Ppm => {
+                    std::fs::create_dir_all(&self.path).map_err(|err| err.to_string())?;
+                }
+            }
+
+            self.canvas = Some(canvas);
+        }
+
+        let canvas = self.canvas.as_ref().unwrap();
+        let image = canvas.render(frame, self.scale);
+        let (width, height) = (canvas.width * self.scale, canvas.height * self.scale);
+        let idx = self.frames;
+
+        match (self.format, &mut self.gif) {
+            (RecordFormat::Gif, Some(encoder)) => write_gif_frame(encoder, image, width, height),
+            (RecordFormat::Png, _) => {
+                let path = self.path.join(format!("frame_{idx:05}.png"));
+                write_png(&path, &image, width, height)
+            }
+            (RecordFormat::Ppm, _) => {
+                let path = self.path.join(format!("frame_{idx:05}.ppm"));
+                write_ppm(&path, &image, width, height)
+            }
+            (RecordFormat::Gif, None) => unreachable!("the GIF is created with the first frame"),
+        }
+    }
+}
+
+impl FrameSink for FrameWriter {
+    fn write_frame(&mut self, frame: Frame) -> Result<(), String> {
+        self.write(&frame).map_err(|err| self.error(err))?;
+
+        self.frames += 1;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), String> {
+        if self.frames == 0 {
+            return Err(
+                "the solution didn't record any frames, only simulations can be recorded"
+                    .to_string(),
+            );
+        }
+
+        // Dropping the encoder would write the end of the GIF but ignore any error doing so
+        if let Some(encoder) = self.gif.take() {
+            encoder
+                .into_inner()
+                .and_then(|mut file| file.flush())
+                .map_err(|err| self.error(err))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn create_gif(
+    path: &Path,
+    canvas: &Canvas,
+    scale: usize,
+) -> Result<gif::Encoder<BufWriter<File>>, String> {
+    let width =
+        u16::try_from(canvas.width * scale).map_err(|_| "the frames are too wide for a GIF")?;
+    let height =
+        u16::try_from(canvas.height * scale).map_err(|_| "the frames are too tall for a GIF")?;
+
+    // Every frame brings its own palette, as the colors used by later frames aren't known yet
+    let file = File::create(path).map_err(|err| err.to_string())?;
+    let mut encoder = gif::Encoder::new(BufWriter::new(file), width, height, &[])
+        .map_err(|err| err.to_string())?;
+    encoder.set_repeat(gif::Repeat::Infinite).map_err(|err| err.to_string())?;
+
+    Ok(encoder)
+}
+
+fn write_gif_frame(
+    encoder: &mut gif::Encoder<BufWriter<File>>,
+    image: Vec<Rgb>,
+    width: usize,
+    height: usize,
+) -> Result<(), String> {
+    // The simulations only draw with a handful of colors, so rather than quantizing the frame it
+    // gets an exact palette.
+    let mut palette: HashMap<Rgb, u8> = HashMap::new();
+    let mut indices = Vec::with_capacity(image.len());
+
+    for color in image {
+        let index = match palette.get(&color) {
+            Some(&index) => index,
+            None => {
+                let index =
+                    u8::try_from(palette.len()).map_err(|_| "a frame uses more than 256 colors")?;
+
+                palette.insert(color, index);
+                index
+            }
+        };
+
+        indices.push(index);
+    }
+
+    let mut colors = vec![[0; 3]; palette.len()];
+    for (color, index) in palette {
+        colors[index as usize] = color;
+    }
+
+    let mut gif_frame =
+        gif::Frame::from_indexed_pixels(width as u16, height as u16, indices, None);
+    gif_frame.palette = Some(colors.as_flattened().to_vec());
+    gif_frame.delay = FRAME_DELAY;
+
+    encoder.write_frame(&gif_frame).map_err(|err| err.to_string())
+}
+
+fn write_png(path: &Path, image: &[Rgb], width: usize, height: usize) -> Result<(), String> {
+    let file = File::create(path).map_err(|err| err.to_string())?;
+
+    let mut encoder = png::Encoder::new(BufWriter::new(file), width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header().map_err(|err| err.to_string())?;
+    writer.write_image_data(image.as_flattened()).map_err(|err| err.to_string())
+}
+
+fn write_ppm(path: &Path, image: &[Rgb], width: usize, height: usize) -> Result<(), String> {
+    let mut file = BufWriter::new(File::create(path).map_err(|err| err.to_string())?);
+
+    write!(file, "P6\n{width} {height}\n255\n")
+        .and_then(|_| file.write_all(image.as_flattened()))
+        .and_then(|_| file.flush())
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_common::Recorder;
+
+    fn solid(width: usize, height: usize, color: Rgb) -> Frame {
+        Frame::from_fn(width, height, |_, _| color)
+    }
+
+    /// Writes the frames with a writer for the options, returning the number written.
+    fn save(frames: &[Frame], options: &RecordOptions) -> Result<usize, String> {
+        let recorder = Recorder::new(1, FrameWriter::new(options));
+
+        for frame in frames {
+            recorder.step(|| frame.clone());
+        }
+
+        recorder.close()
+    }
+
+    #[test]
+    fn test_canvas_set_by_first_frame() {
+        let frames = [
+            solid(3, 2, [1, 1, 1]).with_origin(1, 0),
+            solid(4, 1, [2, 2, 2]).with_origin(2, 1),
+        ];
+
+        let canvas = Canvas::of(&frames[0]);
+        assert_eq!(canvas, Canvas { left: 1, top: 0, width: 3, height: 2 });
+
+        let image = canvas.render(&frames[0], 1);
+        assert_eq!(image, vec![[1, 1, 1]; 6]);
+
+        // The part of the frame beyond the right edge of the canvas is left out
+        let scaled = canvas.render(&frames[1], 2);
+        assert_eq!(scaled.len(), 24);
+        let row = [BACKGROUND, BACKGROUND, [2, 2, 2], [2, 2, 2], [2, 2, 2], [2, 2, 2]];
+        assert_eq!(&scaled[12..18], &row);
+        assert_eq!(&scaled[..12], &[BACKGROUND; 12]);
+    }
+
+    #[test]
+    fn test_save_recordings() {
+        let dir = std::env::temp_dir().join(format!("aoc-record-test-{}", std::process::id()));
+        let frames = [solid(2, 1, [255, 0, 0]), solid(2, 1, [0, 0, 255])];
+
+        let ppm = RecordOptions {
+            path: dir.join("ppm"),
+            every: 1,
+            limit: 100,
+            format: RecordFormat::Ppm,
+            scale: 1,
+        };
+        assert_eq!(save(&frames, &ppm), Ok(2));
+        assert_eq!(
+            std::fs::read(dir.join("ppm").join("frame_00001.ppm")).unwrap(),
+            b"P6\n2 1\n255\n\x00\x00\xff\x00\x00\xff"
+        );
+
+        let gif = RecordOptions {
+            path: dir.join("recording.gif"),
+            every: 1,
+            limit: 100,
+            format: RecordFormat::Gif,
+            scale: 3,
+        };
+        assert_eq!(save(&frames, &gif), Ok(2));
+
+        let mut decoder = gif::DecodeOptions::new()
+            .read_info(File::open(dir.join("recording.gif")).unwrap())
+            .unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (6, 3));
+
+        let mut decoded = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert!(frame.palette.is_some());
+            decoded += 1;
+        }
+        assert_eq!(decoded, 2);
+
+        let png = RecordOptions {
+            path: dir.join("png"),
+            every: 1,
+            limit: 100,
+            format: RecordFormat::Png,
+            scale: 1,
+        };
+        assert_eq!(save(&frames, &png), Ok(2));
+        assert!(dir.join("png").join("frame_00000.png").exists());
+
+        assert!(save(&[], &png).unwrap_err().contains("didn't record any frames"));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_record_long_simulation() {
+        let dir = std::env::temp_dir().join(format!("aoc-record-long-test-{}", std::process::id()));
+        let options = RecordOptions {
+            path: dir.clone(),
+            every: 7,
+            limit: 20,
+            format: RecordFormat::Ppm,
+            scale: 1,
+        };
+
+        let recorder = Recorder::new(options.every, FrameWriter::new(&options)).with_limit(20);
+        let frame = |step: usize| solid(64, 64, [(step % 256) as u8, 0, 0]);
+
+        for step in 0..1_000_000 {
+            recorder.step(|| frame(step));
+
+            // Frames are written as they're captured, not once the simulation is over
+            if step == 7 {
+                assert!(dir.join("frame_00001.ppm").exists());
+            }
+        }
+        recorder.finish(|| frame(1_000_000));
+
+        assert_eq!(recorder.close(), Ok(20));
+        assert!(recorder.truncated());
+
+        let written = std::fs::read_dir(&dir).unwrap().count();
+        assert_eq!(written, 20);
+
+        // The last frame is the final state of the simulation, 1_000_000 % 256 = 64
+        let last = std::fs::read(dir.join("frame_00019.ppm")).unwrap();
+        assert_eq!(&last[last.len() - 3..], &[64, 0, 0]);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,221 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use aoc_common::{content_hash, display_answer, InputSource, Part, Progress, Recorder};
+use clap::ValueEnum;
+
+use crate::cache::AnswerCache;
+use crate::days::{Checkpoints, Day, DayReport};
+use crate::output::DayRecord;
+use crate::params::{ParamOverride, ParamsFile};
+use crate::record::{FrameWriter, RecordOptions};
+use crate::timing::TimingReport;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+pub struct RunOptions<'a> {
+    pub part: Option<Part>,
+    pub input: Option<&'a str>,
+
+    pub params: &'a ParamsFile,
+    /// Parameters given on the command line, taking precedence over the file.
+    pub param_overrides: &'a [ParamOverride],
+
+    /// Number of times to repeat the solution when timing it, `None` when not timing.
+    pub timed_runs: Option<usize>,
+    /// How the answers, and timing report if there is one, are printed.
+    pub format: Format,
+
+    /// How often to report the progress of long running solvers, `None` to stay quiet.
+    pub progress_interval: Option<Duration>,
+    /// Give up on any part that hasn't finished after this long.
+    pub timeout: Option<Duration>,
+
+    /// Report any changes made to the input before it was parsed, such as converting CRLF line
+    /// endings.
+    pub report_normalization: bool,
+
+    /// How often long running solvers save a checkpoint, `None` when checkpointing is disabled.
+    pub checkpoint_interval: Option<Duration>,
+    pub checkpoint_dir: PathBuf,
+
+    /// Record the frames of the simulation while solving, `None` when not recording.
+    pub record: Option<RecordOptions>,
+
+    /// Answers from previous runs to reuse, `None` to always solve the puzzle.
+    pub cache: Option<AnswerCache>,
+}
+
+pub fn run(day: u8, options: RunOptions) -> ExitCode {
+    let Some(day) = Day::find(day) else {
+        eprintln!("day {day} doesn't have a solution yet");
+        return ExitCode::FAILURE;
+    };
+
+    let parts = match options.part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+
+    let source = InputSource::from_arg(options.input, day.data_dir);
+    let data = match source.read() {
+        Ok(data) => data,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let params = match options.params.for_source(day, &source, options.param_overrides) {
+        Ok(params) => params,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let progress = build_progress(day.number, &options);
+    let checkpoints = options.checkpoint_interval.map(|interval| Checkpoints {
+        dir: options.checkpoint_dir.clone(),
+        interval,
+    });
+
+    let recorder = options.record.as_ref().map(|record| {
+        Recorder::new(record.every, FrameWriter::new(record)).with_limit(record.limit)
+    });
+
+    let mut reports: Vec<DayReport> = vec![];
+
+    for _ in 0..options.timed_runs.unwrap_or(1).max(1) {
+        let result = params.scope(|| match &recorder {
+            Some(recorder) => recorder.scope(|| {
+                day.run(&data, &parts, &progress, checkpoints.as_ref(), options.cache.as_ref())
+            }),
+            None => day.run(&data, &parts, &progress, checkpoints.as_ref(), options.cache.as_ref()),
+        });
+
+        match result {
+            Ok(report) => reports.push(report),
+            Err(err) => {
+                eprintln!("failed to parse puzzle input {source}");
+                eprintln!("{}", err.render());
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let report = reports.last().unwrap();
+
+    if let (Some(recorder), Some(record)) = (&recorder, &options.record) {
+        match recorder.close() {
+            Ok(frames) => {
+                let path = record.path.display();
+                eprintln!("Day {:02}: recorded {frames} frames to {path}", day.number);
+
+                if recorder.truncated() {
+                    eprintln!(
+                        "Day {:02}: stopped capturing after {} frames, raise --record-every or \
+                         --record-limit to record the whole simulation",
+                        day.number, record.limit
+                    );
+                }
+            }
+            Err(err) => {
+                eprintln!("Day {:02}: {err}", day.number);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    if options.report_normalization {
+        for change in &report.normalizations {
+            eprintln!("Day {:02}: input normalized, {change}", day.number);
+        }
+    }
+    let timing = options
+        .timed_runs
+        .map(|_| TimingReport::from_runs(day.number, &reports));
+
+    match options.format {
+        Format::Text => {
+            print_answers(day, report);
+
+            if let Some(timing) = timing {
+                println!("{}", timing.display_table());
+            }
+        }
+        Format::Json => {
+            let mut record =
+                DayRecord::new(day.number, source.to_string(), content_hash(&data), report);
+
+            if let Some(timing) = timing {
+                record = record.with_timing(timing);
+            }
+
+            println!("{}", serde_json::to_string(&record).unwrap());
+        }
+    }
+
+    if report.parts.iter().any(|p| p.interrupted.is_some()) {
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// Sets up the progress handle for the run, including cancelling it when Ctrl-C is pressed. The
+/// first Ctrl-C lets the solver stop cleanly and report what it got through, a second one exits
+/// immediately.
+fn build_progress(day: u8, options: &RunOptions) -> Progress {
+    let mut progress = Progress::new();
+
+    if let Some(interval) = options.progress_interval {
+        progress = progress.with_reporter(interval, move |update| {
+            eprintln!("Day {day:02}: {update}");
+        });
+    }
+
+    if let Some(timeout) = options.timeout {
+        progress = progress.with_timeout(timeout);
+    }
+
+    let handler_progress = progress.clone();
+    let handler = ctrlc::set_handler(move || {
+        if handler_progress.should_stop() {
+            std::process::exit(130);
+        }
+
+        eprintln!("stopping, press Ctrl-C again to exit immediately");
+        handler_progress.cancel();
+    });
+
+    if let Err(err) = handler {
+        eprintln!("unable to handle Ctrl-C, it will exit immediately: {err}");
+    }
+
+    progress
+}
+
+fn print_answers(day: &Day, report: &DayReport) {
+    println!("Day {:02} (parsed in {:?})", day.number, report.parse_time);
+
+    for part_report in &report.parts {
+        let answer = match &part_report.interrupted {
+            Some(interrupted) => format!("interrupted, {interrupted}"),
+            None => display_answer(part_report.answer.as_deref()),
+        };
+
+        let elapsed = match part_report.cached {
+            true => "cached".to_string(),
+            false => format!("{:?}", part_report.elapsed),
+        };
+
+        println!("  Part {} ({elapsed}): {answer}", part_report.part);
+    }
+}
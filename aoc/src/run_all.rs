@@ -0,0 +1,372 @@
+use std::any::Any;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc_common::{content_hash, InputSource, Part, Progress};
+use serde::Serialize;
+
+use crate::cache::AnswerCache;
+use crate::days::{Day, DayReport, DAYS};
+use crate::output::DayRecord;
+use crate::params::ParamsFile;
+use crate::run::Format;
+
+pub struct RunAllOptions {
+    pub part: Option<Part>,
+    pub format: Format,
+
+    /// Give up on any part that hasn't finished after this long.
+    pub timeout: Option<Duration>,
+    /// Number of days solved at the same time, by default one per available core.
+    pub jobs: Option<usize>,
+
+    pub params: ParamsFile,
+    /// Answers from previous runs to reuse, `None` to always solve the puzzles.
+    pub cache: Option<AnswerCache>,
+}
+
+/// What happened when solving a single day of the batch.
+struct DayOutcome {
+    day: u8,
+    /// Where the input was read from along with a hash of it, `None` when it couldn't be read.
+    input: Option<(String, String)>,
+    elapsed: Duration,
+    /// The day's report, or why the day couldn't be solved at all.
+    result: Result<DayReport, String>,
+}
+
+impl DayOutcome {
+    /// Whether the day failed to parse or panicked, or any of its parts were stopped early.
+    fn failed(&self) -> bool {
+        match &self.result {
+            Ok(report) => report.parts.iter().any(|p| p.interrupted.is_some()),
+            Err(_) => true,
+        }
+    }
+}
+
+/// Printed by `--format json` in place of the day's record when it couldn't be solved.
+#[derive(Serialize)]
+struct FailedDay {
+    day: u8,
+    error: String,
+}
+
+/// Solves every day at once on a pool of worker threads and prints a summary of all of them. A
+/// day that fails to parse or panics is reported as failed without affecting the others.
+pub fn run_all(options: RunAllOptions) -> ExitCode {
+    let parts = match options.part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+
+    let jobs = options.jobs.unwrap_or_else(|| {
+        thread::available_parallelism().map_or(1, |jobs| jobs.get())
+    });
+
+    // Every day's progress handle, so Ctrl-C can stop the days that are running and the ones
+    // that start afterwards know not to bother
+    let started: Arc<Mutex<Vec<Progress>>> = Arc::default();
+    let stopping = Arc::new(AtomicBool::new(false));
+
+    let (handler_started, handler_stopping) = (started.clone(), stopping.clone());
+    let handler = ctrlc::set_handler(move || {
+        if handler_stopping.swap(true, Ordering::SeqCst) {
+            std::process::exit(130);
+        }
+
+        eprintln!("stopping, press Ctrl-C again to exit immediately");
+        handler_started.lock().unwrap().iter().for_each(Progress::cancel);
+    });
+
+    if let Err(err) = handler {
+        eprintln!("unable to handle Ctrl-C, it will exit immediately: {err}");
+    }
+
+    let batch_start = Instant::now();
+    let outcomes = solve_days(DAYS, jobs, |day| {
+        let mut progress = Progress::new();
+
+        if let Some(timeout) = options.timeout {
+            progress = progress.with_timeout(timeout);
+        }
+
+        {
+            let mut started = started.lock().unwrap();
+            started.push(progress.clone());
+
+            if stopping.load(Ordering::SeqCst) {
+                progress.cancel();
+            }
+        }
+
+        solve_day(day, &parts, &progress, &options.params, options.cache.as_ref())
+    });
+    let batch_time = batch_start.elapsed();
+
+    match options.format {
+        Format::Text => {
+            println!("{}", summary_table(&outcomes));
+
+            let failed = outcomes.iter().filter(|o| o.failed()).count();
+            println!(
+                "{} days solved in {batch_time:.2?}, {jobs} at a time, {failed} failed",
+                outcomes.len()
+            );
+        }
+        Format::Json => {
+            for outcome in &outcomes {
+                let line = match &outcome.result {
+                    Ok(report) => {
+                        let (source, hash) = outcome.input.clone().unwrap_or_default();
+                        serde_json::to_string(&DayRecord::new(outcome.day, source, hash, report))
+                    }
+                    Err(error) => serde_json::to_string(&FailedDay {
+                        day: outcome.day,
+                        error: error.clone(),
+                    }),
+                };
+
+                println!("{}", line.unwrap());
+            }
+        }
+    }
+
+    if outcomes.iter().any(DayOutcome::failed) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Solves the days on `jobs` worker threads, each taking the next unsolved day until they're all
+/// done. The outcomes are returned in calendar order whatever order they finished in.
+fn solve_days(
+    days: &'static [Day],
+    jobs: usize,
+    solve: impl Fn(&'static Day) -> DayOutcome + Sync,
+) -> Vec<DayOutcome> {
+    let next = AtomicUsize::new(0);
+    let outcomes = Mutex::new(Vec::with_capacity(days.len()));
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, days.len().max(1)) {
+            scope.spawn(|| {
+                while let Some(day) = days.get(next.fetch_add(1, Ordering::SeqCst)) {
+                    let outcome = solve(day);
+                    outcomes.lock().unwrap().push(outcome);
+                }
+            });
+        }
+    });
+
+    let mut outcomes = outcomes.into_inner().unwrap();
+    outcomes.sort_by_key(|o| o.day);
+
+    outcomes
+}
+
+/// Solves a day from its bundled input, catching any panic so it's reported like any other
+/// failure.
+fn solve_day(
+    day: &Day,
+    parts: &[Part],
+    progress: &Progress,
+    params: &ParamsFile,
+    cache: Option<&AnswerCache>,
+) -> DayOutcome {
+    let start = Instant::now();
+    let source = InputSource::from_arg(None, day.data_dir);
+
+    let data = match source.read() {
+        Ok(data) => data,
+        Err(err) => {
+            return DayOutcome {
+                day: day.number,
+                input: None,
+                elapsed: start.elapsed(),
+                result: Err(err.to_string()),
+            };
+        }
+    };
+
+    let result = isolated(|| {
+        let params = params.for_source(day, &source, &[])?;
+
+        params
+            .scope(|| day.run(&data, parts, progress, None, cache))
+            .map_err(|err| format!("failed to parse puzzle input, {err}"))
+    });
+
+    DayOutcome {
+        day: day.number,
+        input: Some((source.to_string(), content_hash(&data))),
+        elapsed: start.elapsed(),
+        result,
+    }
+}
+
+/// Runs the closure, turning a panic into an error describing it.
+fn isolated<T>(f: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| Err(panic_message(payload)))
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    let message = payload.downcast_ref::<&str>().map(|m| m.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown cause".to_string());
+
+    format!("panicked, {message}")
+}
+
+/// Lays the outcome of every day out as a table with a row for each part. Answers spanning
+/// several lines, such as day 10's CRT, are printed in full after the table.
+fn summary_table(outcomes: &[DayOutcome]) -> String {
+    let mut rows: Vec<[String; 4]> = vec![];
+    let mut displays = vec![];
+
+    for outcome in outcomes {
+        let day = format!("{:02}", outcome.day);
+
+        let report = match &outcome.result {
+            Ok(report) => report,
+            Err(err) => {
+                let elapsed = format!("{:.2?}", outcome.elapsed);
+                rows.push([day, "-".to_string(), format!("FAILED: {err}"), elapsed]);
+                continue;
+            }
+        };
+
+        for part_report in &report.parts {
+            let answer = match (&part_report.interrupted, part_report.answer.as_deref()) {
+                (Some(interrupted), _) => format!("FAILED: interrupted, {interrupted}"),
+                (None, Some(answer)) if answer.contains('\n') => {
+                    displays.push(format!("Day {day} part {}:\n{answer}", part_report.part));
+                    "(shown below)".to_string()
+                }
+                (None, Some(answer)) => answer.to_string(),
+                (None, None) => "no answer".to_string(),
+            };
+
+            let elapsed = match part_report.cached {
+                true => "cached".to_string(),
+                false => format!("{:.2?}", part_report.elapsed),
+            };
+
+            rows.push([day.clone(), part_report.part.to_string(), answer, elapsed]);
+        }
+    }
+
+    let header = ["day", "part", "answer", "time"].map(str::to_string);
+    let answer_width = rows.iter().chain([&header]).map(|r| r[2].chars().count()).max();
+    let answer_width = answer_width.unwrap_or(0);
+
+    let mut table: Vec<String> = [&header]
+        .into_iter()
+        .chain(&rows)
+        .map(|[day, part, answer, time]| {
+            format!("{day:<4} {part:<4} {answer:<answer_width$} {time:>12}")
+        })
+        .collect();
+
+    table.extend(displays);
+
+    table.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::days::PartReport;
+
+    fn solved(day: u8, answers: &[Option<&str>]) -> DayOutcome {
+        let parts = answers
+            .iter()
+            .zip(Part::ALL)
+            .map(|(answer, part)| PartReport {
+                part,
+                answer: answer.map(str::to_string),
+                elapsed: Duration::from_millis(1),
+
+                interrupted: None,
+                cached: false,
+            })
+            .collect();
+
+        DayOutcome {
+            day,
+            input: None,
+            elapsed: Duration::from_millis(2),
+            result: Ok(DayReport {
+                normalizations: vec![],
+                parse_time: Duration::ZERO,
+                parts,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_panics_are_isolated() {
+        let result: Result<(), String> = isolated(|| panic!("not implemented"));
+        assert_eq!(result, Err("panicked, not implemented".to_string()));
+
+        let result: Result<(), String> = isolated(|| panic!("day {} is broken", 25));
+        assert_eq!(result, Err("panicked, day 25 is broken".to_string()));
+
+        assert_eq!(isolated(|| Ok(7)), Ok(7));
+    }
+
+    #[test]
+    fn test_solve_days_in_order() {
+        let outcomes = solve_days(DAYS, 3, |day| {
+            // Later days finish first to check the outcomes are sorted afterwards
+            thread::sleep(Duration::from_millis(30 - day.number as u64));
+
+            let mut outcome = solved(day.number, &[Some("1")]);
+            if day.number == 25 {
+                outcome.result = isolated(|| unimplemented!());
+            }
+
+            outcome
+        });
+
+        let days: Vec<u8> = outcomes.iter().map(|o| o.day).collect();
+        assert_eq!(days, DAYS.iter().map(|d| d.number).collect::<Vec<_>>());
+
+        let failed: Vec<u8> = outcomes.iter().filter(|o| o.failed()).map(|o| o.day).collect();
+        assert_eq!(failed, vec![25]);
+    }
+
+    #[test]
+    fn test_summary_table() {
+        let mut broken = solved(3, &[]);
+        broken.result = Err("panicked, not implemented".to_string());
+
+        let outcomes = [
+            solved(1, &[Some("24000"), Some("45000")]),
+            broken,
+            solved(10, &[Some("13140"), Some("##..\n#.#.")]),
+        ];
+
+        assert_eq!(
+            summary_table(&outcomes),
+            [
+                "day  part answer                                    time",
+                "01   1    24000                                   1.00ms",
+                "01   2    45000                                   1.00ms",
+                "03   -    FAILED: panicked, not implemented       2.00ms",
+                "10   1    13140                                   1.00ms",
+                "10   2    (shown below)                           1.00ms",
+                "Day 10 part 2:",
+                "##..",
+                "#.#.",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use crate::fetch::WORKSPACE_DIR;
+
+const CARGO_TEMPLATE: &str = include_str!("../templates/day/Cargo.toml.tmpl");
+const LIB_TEMPLATE: &str = include_str!("../templates/day/lib.rs.tmpl");
+const MAIN_TEMPLATE: &str = include_str!("../templates/day/main.rs.tmpl");
+
+/// Creates the crate for a new day and registers it with the workspace and the runner.
+pub fn new_day(day: u8) -> ExitCode {
+    if !(1..=25).contains(&day) {
+        eprintln!("there is no day {day} in the advent calendar");
+        return ExitCode::FAILURE;
+    }
+
+    match scaffold(Path::new(WORKSPACE_DIR), day) {
+        Ok(created) => {
+            for path in created {
+                println!("created {}", path.display());
+            }
+
+            println!("Day {day:02} is ready, `aoc fetch {day}` will download its input");
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("unable to create day {day}: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Writes the new crate from the templates, with empty `data/sample` and `data/input` files to
+/// fill in, then adds it to the workspace members, the runner's dependencies and the runner's
+/// list of days. Returns the files that were created.
+fn scaffold(workspace: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    let name = format!("day_{day:02}");
+    let crate_dir = workspace.join(&name);
+
+    if crate_dir.exists() {
+        return Err(format!("{} already exists", crate_dir.display()));
+    }
+
+    // Registering is checked before anything is written so a failure doesn't leave a half
+    // created day behind.
+    let workspace_manifest = workspace.join("Cargo.toml");
+    let workspace_contents = insert_sorted(
+        &read(&workspace_manifest)?,
+        |line| line.trim() == "members = [",
+        |line| line.trim() == "]",
+        &format!("    \"{name}\","),
+    )?;
+
+    let runner_manifest = workspace.join("aoc").join("Cargo.toml");
+    let runner_contents = insert_sorted(
+        &read(&runner_manifest)?,
+        |line| line.trim() == "[dependencies]",
+        |line| line.starts_with('['),
+        &format!("{name} = {{ path = \"../{name}\" }}"),
+    )?;
+
+    let days_source = workspace.join("aoc").join("src").join("days.rs");
+    let days_contents = insert_sorted(
+        &read(&days_source)?,
+        |line| line.starts_with("pub const DAYS"),
+        |line| line.trim() == "];",
+        &format!("    Day::new::<{name}::Day{day:02}>(),"),
+    )?;
+
+    let render = |template: &str| {
+        template
+            .replace("{{day}}", &format!("{day:02}"))
+            .replace("{{number}}", &day.to_string())
+    };
+
+    let files = [
+        (crate_dir.join("Cargo.toml"), render(CARGO_TEMPLATE)),
+        (crate_dir.join("src").join("lib.rs"), render(LIB_TEMPLATE)),
+        (crate_dir.join("src").join("main.rs"), render(MAIN_TEMPLATE)),
+        (crate_dir.join("data").join("sample"), String::new()),
+        (crate_dir.join("data").join("input"), String::new()),
+    ];
+
+    let mut created = vec![];
+
+    for (path, contents) in files {
+        std::fs::create_dir_all(path.parent().unwrap())
+            .and_then(|_| std::fs::write(&path, contents))
+            .map_err(|err| format!("unable to write {}: {err}", path.display()))?;
+
+        created.push(path);
+    }
+
+    write(&workspace_manifest, &workspace_contents)?;
+    write(&runner_manifest, &runner_contents)?;
+    write(&days_source, &days_contents)?;
+
+    Ok(created)
+}
+
+/// Adds a line to a list of lines kept in sorted order, such as the workspace members. The list
+/// is the lines after the first line matching `is_start`, up to the line matching `is_end` or the
+/// end of the file. Only lines with the same indentation as the new line are considered part of
+/// the list so comments and other entries are left where they are.
+fn insert_sorted(
+    contents: &str,
+    is_start: impl Fn(&str) -> bool,
+    is_end: impl Fn(&str) -> bool,
+    new_line: &str,
+) -> Result<String, String> {
+    let mut lines: Vec<&str> = contents.lines().collect();
+
+    let start = lines
+        .iter()
+        .position(|line| is_start(line))
+        .ok_or_else(|| format!("couldn't find where to add {:?}", new_line.trim()))?;
+    let end = lines[start + 1..]
+        .iter()
+        .position(|line| is_end(line))
+        .map_or(lines.len(), |offset| start + 1 + offset);
+
+    let indent = new_line.len() - new_line.trim_start().len();
+    let is_entry = |line: &str| {
+        !line.trim().is_empty()
+            && !line.trim_start().starts_with('#')
+            && line.len() - line.trim_start().len() == indent
+    };
+
+    if lines[start + 1..end].contains(&new_line) {
+        return Err(format!("{:?} is already registered", new_line.trim()));
+    }
+
+    let entries: Vec<usize> = (start + 1..end).filter(|&idx| is_entry(lines[idx])).collect();
+
+    // Only the entries for other days are ordered against, anything listed before them (such as
+    // the runner's other dependencies) stays first.
+    let position = entries
+        .iter()
+        .find(|&&idx| lines[idx].trim_start().contains("day_") && lines[idx] > new_line)
+        .copied()
+        .or_else(|| entries.last().map(|idx| idx + 1))
+        .unwrap_or(end);
+
+    lines.insert(position, new_line);
+
+    let mut output = lines.join("\n");
+    if contents.ends_with('\n') {
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|err| format!("unable to read {}: {err}", path.display()))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    std::fs::write(path, contents).map_err(|err| format!("unable to write {}: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_sorted() {
+        let members = "[workspace]\nmembers = [\n    \"aoc\",\n    \"day_01\",\n    \"day_14\",\n]\n\nexclude = [\n    \"day_03\",\n]\n";
+
+        let contents = insert_sorted(
+            members,
+            |line| line.trim() == "members = [",
+            |line| line.trim() == "]",
+            "    \"day_07\",",
+        )
+        .unwrap();
+        assert_eq!(
+            contents,
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"day_01\",\n    \"day_07\",\n    \"day_14\",\n]\n\nexclude = [\n    \"day_03\",\n]\n"
+        );
+
+        let contents = insert_sorted(
+            members,
+            |line| line.trim() == "members = [",
+            |line| line.trim() == "]",
+            "    \"day_20\",",
+        )
+        .unwrap();
+        assert!(contents.contains("    \"day_14\",\n    \"day_20\",\n]"));
+
+        let err = insert_sorted(
+            members,
+            |line| line.trim() == "members = [",
+            |line| line.trim() == "]",
+            "    \"day_14\",",
+        )
+        .unwrap_err();
+        assert_eq!(err, "\"\\\"day_14\\\",\" is already registered");
+    }
+
+    #[test]
+    fn test_scaffold_new_day() {
+        let workspace = std::env::temp_dir().join(format!("aoc-scaffold-test-{}", std::process::id()));
+        std::fs::create_dir_all(workspace.join("aoc").join("src")).unwrap();
+
+        std::fs::write(workspace.join("Cargo.toml"), include_str!("../../Cargo.toml")).unwrap();
+        std::fs::write(workspace.join("aoc").join("Cargo.toml"), include_str!("../Cargo.toml")).unwrap();
+        std::fs::write(workspace.join("aoc").join("src").join("days.rs"), include_str!("days.rs")).unwrap();
+
+        let created = scaffold(&workspace, 7).unwrap();
+        assert_eq!(created.len(), 5);
+
+        let lib = std::fs::read_to_string(workspace.join("day_07").join("src").join("lib.rs")).unwrap();
+        assert!(lib.contains("pub struct Day07;"));
+        assert!(lib.contains("const DAY: u8 = 7;"));
+
+        let main = std::fs::read_to_string(workspace.join("day_07").join("src").join("main.rs")).unwrap();
+        assert!(main.contains("aoc_common::run::<day_07::Day07>"));
+
+        assert_eq!(std::fs::read(workspace.join("day_07").join("data").join("input")).unwrap(), b"");
+
+        let members = std::fs::read_to_string(workspace.join("Cargo.toml")).unwrap();
+        assert!(members.contains("    \"day_02\",\n    \"day_07\",\n    \"day_10\",\n"));
+
+        let runner = std::fs::read_to_string(workspace.join("aoc").join("Cargo.toml")).unwrap();
+        assert!(runner.contains("day_02 = { path = \"../day_02\" }\nday_07 = { path = \"../day_07\" }\nday_10"));
+
+        let days = std::fs::read_to_string(workspace.join("aoc").join("src").join("days.rs")).unwrap();
+        assert!(days.contains("day_02::Day02>(),\n    Day::new::<day_07::Day07>(),\n    Day::new::<day_10"));
+
+        // A day can't be created twice
+        assert!(scaffold(&workspace, 7).unwrap_err().ends_with("day_07 already exists"));
+
+        std::fs::remove_dir_all(workspace).unwrap();
+    }
+}
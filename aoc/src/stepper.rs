@@ -0,0 +1,535 @@
+use std::io::{self, BufWriter, Write};
+use std::process::ExitCode;
+use std::time::Duration;
+
+use aoc_common::recorder::Rgb;
+use aoc_common::{Frame, InputSource, Part, Simulation, Step};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor};
+use crossterm::terminal::{self, ClearType};
+use crossterm::{cursor, execute, queue};
+
+use crate::days::Day;
+use crate::params::{ParamOverride, ParamsFile};
+
+/// How often the screen is redrawn while the simulation is playing.
+const FRAME_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Most steps run between redraws while running towards a goal, so long runs still show how far
+/// they got and can be paused.
+const BATCH_SIZE: usize = 10_000;
+
+/// Lines at the bottom of the screen for the status, the simulation's state and the keys.
+const STATUS_LINES: u16 = 3;
+
+/// Most simulation cells shown by a single pixel when zoomed out.
+const MAX_ZOOM: usize = 64;
+
+const KEYS_HELP: &str = "[N]s step  [N]n milestone  Ng go to step  G finish  space play/pause  \
+    </> speed  arrows scroll  +/- zoom  f fit  q quit";
+
+pub struct StepOptions<'a> {
+    pub part: Part,
+    pub input: Option<&'a str>,
+
+    pub params: &'a ParamsFile,
+    /// Parameters given on the command line, taking precedence over the file.
+    pub param_overrides: &'a [ParamOverride],
+}
+
+/// Steps through the simulation solving one part of a day in the terminal.
+pub fn step(day: u8, options: StepOptions) -> ExitCode {
+    let Some(day) = Day::find(day) else {
+        eprintln!("day {day} doesn't have a solution yet");
+        return ExitCode::FAILURE;
+    };
+
+    let source = InputSource::from_arg(options.input, day.data_dir);
+    let data = match source.read() {
+        Ok(data) => data,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let params = match options.params.for_source(day, &source, options.param_overrides) {
+        Ok(params) => params,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let simulation = match params.scope(|| day.simulate(&data, options.part)) {
+        Ok(Some(simulation)) => simulation,
+        Ok(None) => {
+            eprintln!("day {} isn't solved by a simulation to step through", day.number);
+            return ExitCode::FAILURE;
+        }
+        Err(err) => {
+            eprintln!("failed to parse puzzle input {source}");
+            eprintln!("{}", err.render());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let title = format!("Day {:02} part {}", day.number, options.part);
+    let size = terminal::size().unwrap_or((80, 24));
+    let mut stepper = Stepper::new(simulation, size);
+
+    if let Err(err) = params.scope(|| interact(&mut stepper, &title)) {
+        eprintln!("terminal error: {err}");
+        return ExitCode::FAILURE;
+    }
+
+    eprintln!("{title}: stopped after {} steps", stepper.steps);
+
+    ExitCode::SUCCESS
+}
+
+/// Runs the stepper until it's quit, redrawing after every key and batch of steps.
+fn interact(stepper: &mut Stepper, title: &str) -> io::Result<()> {
+    let _terminal = RawTerminal::enter()?;
+    let mut out = BufWriter::new(io::stdout());
+
+    loop {
+        stepper.draw(&mut out, title, terminal::size()?)?;
+        out.flush()?;
+
+        let event = match stepper.goal {
+            Goal::Paused => Some(event::read()?),
+            Goal::Playing => event::poll(FRAME_INTERVAL)?.then(event::read).transpose()?,
+            _ => event::poll(Duration::ZERO)?.then(event::read).transpose()?,
+        };
+
+        if let Some(Event::Key(key)) = event {
+            let interrupt =
+                key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c');
+
+            if interrupt || (key.kind == KeyEventKind::Press && !stepper.handle(key.code)) {
+                return Ok(());
+            }
+        }
+
+        stepper.advance();
+    }
+}
+
+/// Switches the terminal to a raw alternate screen, switching it back when dropped so the
+/// terminal is left usable even when the simulation panics.
+struct RawTerminal;
+
+impl RawTerminal {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+
+        Ok(RawTerminal)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), ResetColor, cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// What the stepper is running the simulation towards.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Goal {
+    Paused,
+    /// Runs the stepper's speed in steps every redraw until paused.
+    Playing,
+    /// Runs until this many steps have been taken in total.
+    Step(usize),
+    /// Runs until this many more milestones have been reached.
+    Milestones(usize),
+    Finished,
+}
+
+/// The part of the simulation shown on screen. Every pixel shows a square of `zoom` cells of the
+/// simulation, and every character two pixels stacked on top of each other.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct View {
+    left: isize,
+    top: isize,
+    zoom: usize,
+}
+
+impl View {
+    /// The view showing the whole frame zoomed in as far as it fits on a screen of this many
+    /// columns and rows of pixels.
+    fn fit(frame: &Frame, columns: usize, rows: usize) -> View {
+        let mut zoom = 1;
+
+        while zoom < MAX_ZOOM
+            && (frame.width.div_ceil(zoom) > columns || frame.height.div_ceil(zoom) > rows)
+        {
+            zoom *= 2;
+        }
+
+        View {
+            left: frame.left,
+            top: frame.top,
+            zoom,
+        }
+    }
+
+    /// Color of the pixel at the column and row of the screen, `None` when it's outside of the
+    /// frame. Zoomed out pixels show the brightest cell they cover so small details such as a
+    /// single grain of sand don't disappear.
+    fn pixel(&self, frame: &Frame, column: usize, row: usize) -> Option<Rgb> {
+        let left = self.left + (column * self.zoom) as isize - frame.left;
+        let top = self.top + (row * self.zoom) as isize - frame.top;
+
+        let columns = left.max(0)..(left + self.zoom as isize).min(frame.width as isize);
+        let rows = top.max(0)..(top + self.zoom as isize).min(frame.height as isize);
+
+        rows.flat_map(|y| columns.clone().map(move |x| (x as usize, y as usize)))
+            .map(|(x, y)| frame.pixel(x, y))
+            .max_by_key(|color| color.iter().map(|&c| c as u16).sum::<u16>())
+    }
+}
+
+struct Stepper {
+    simulation: Box<dyn Simulation>,
+
+    steps: usize,
+    milestones: usize,
+    finished: bool,
+
+    goal: Goal,
+    /// Steps run every redraw while playing.
+    speed: usize,
+    /// Number typed before a key, repeating the key or giving it a target.
+    count: Option<usize>,
+
+    view: View,
+    /// Shown in the status line until the next key, for keys that couldn't do anything.
+    message: Option<String>,
+}
+
+impl Stepper {
+    /// Sets up a paused stepper with the whole simulation in view on a screen of this size.
+    fn new(simulation: Box<dyn Simulation>, (columns, rows): (u16, u16)) -> Self {
+        let grid_rows = rows.saturating_sub(STATUS_LINES).max(1) as usize * 2;
+        let view = View::fit(&simulation.frame(), columns.max(1) as usize, grid_rows);
+
+        Stepper {
+            simulation,
+
+            steps: 0,
+            milestones: 0,
+            finished: false,
+
+            goal: Goal::Paused,
+            speed: 1,
+            count: None,
+
+            view,
+            message: None,
+        }
+    }
+
+    /// Runs the simulation towards the goal, at most one redraw's worth of steps at a time. The
+    /// stepper pauses once the goal is reached or the simulation finishes.
+    fn advance(&mut self) {
+        let budget = match self.goal {
+            Goal::Paused => return,
+            Goal::Playing => self.speed,
+            _ => BATCH_SIZE,
+        };
+
+        for _ in 0..budget {
+            if self.finished || self.goal == Goal::Step(self.steps) {
+                self.goal = Goal::Paused;
+                return;
+            }
+
+            match self.simulation.step() {
+                Step::Running => (),
+                Step::Milestone => {
+                    self.milestones += 1;
+
+                    if let Goal::Milestones(remaining) = &mut self.goal {
+                        *remaining -= 1;
+
+                        if *remaining == 0 {
+                            self.goal = Goal::Paused;
+                        }
+                    }
+                }
+                Step::Finished => {
+                    self.finished = true;
+                    self.goal = Goal::Paused;
+                    return;
+                }
+            }
+
+            self.steps += 1;
+
+            if self.goal == Goal::Paused {
+                return;
+            }
+        }
+    }
+
+    /// Handles a key press, returning false when the stepper should quit.
+    fn handle(&mut self, key: KeyCode) -> bool {
+        self.message = None;
+
+        if let KeyCode::Char(digit @ '0'..='9') = key {
+            let digit = digit as usize - '0' as usize;
+            self.count = Some(self.count.unwrap_or(0).saturating_mul(10).saturating_add(digit));
+
+            return true;
+        }
+
+        let count = self.count.take();
+        let scroll_columns = 8 * self.view.zoom as isize;
+        let scroll_rows = 4 * self.view.zoom as isize;
+
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+
+            KeyCode::Char(' ') if self.goal != Goal::Paused => self.goal = Goal::Paused,
+            KeyCode::Char(' ') => self.start(Goal::Playing),
+            KeyCode::Char('s') | KeyCode::Char('.') => {
+                self.start(Goal::Step(self.steps + count.unwrap_or(1)))
+            }
+            KeyCode::Char('n') => self.start(Goal::Milestones(count.unwrap_or(1).max(1))),
+            KeyCode::Char('g') => match count {
+                Some(target) if target >= self.steps => self.start(Goal::Step(target)),
+                Some(target) => self.message = Some(format!("already past step {target}")),
+                None => self.message = Some("type the step to go to before g".to_string()),
+            },
+            KeyCode::Char('G') => self.start(Goal::Finished),
+
+            KeyCode::Char('<') => self.speed = (self.speed / 2).max(1),
+            KeyCode::Char('>') => self.speed = (self.speed * 2).min(BATCH_SIZE),
+
+            KeyCode::Left | KeyCode::Char('h') => self.view.left -= scroll_columns,
+            KeyCode::Right | KeyCode::Char('l') => self.view.left += scroll_columns,
+            KeyCode::Up | KeyCode::Char('k') => self.view.top -= scroll_rows,
+            KeyCode::Down | KeyCode::Char('j') => self.view.top += scroll_rows,
+            KeyCode::Char('+') | KeyCode::Char('=') => self.view.zoom = (self.view.zoom / 2).max(1),
+            KeyCode::Char('-') => self.view.zoom = (self.view.zoom * 2).min(MAX_ZOOM),
+            KeyCode::Char('f') => {
+                let (columns, rows) = terminal::size().unwrap_or((80, 24));
+                let grid_rows = rows.saturating_sub(STATUS_LINES).max(1) as usize * 2;
+
+                self.view = View::fit(&self.simulation.frame(), columns.max(1) as usize, grid_rows);
+            }
+
+            _ => (),
+        }
+
+        true
+    }
+
+    fn start(&mut self, goal: Goal) {
+        if self.finished {
+            self.message = Some("the simulation has finished".to_string());
+        } else {
+            self.goal = goal;
+        }
+    }
+
+    fn draw(
+        &self,
+        out: &mut impl Write,
+        title: &str,
+        (columns, rows): (u16, u16),
+    ) -> io::Result<()> {
+        let frame = self.simulation.frame();
+        let color = |pixel: Option<Rgb>| match pixel {
+            Some([r, g, b]) => Color::Rgb { r, g, b },
+            None => Color::Reset,
+        };
+
+        for line in 0..rows.saturating_sub(STATUS_LINES) {
+            queue!(out, cursor::MoveTo(0, line))?;
+            let mut previous = None;
+
+            for column in 0..columns as usize {
+                let upper = self.view.pixel(&frame, column, line as usize * 2);
+                let lower = self.view.pixel(&frame, column, line as usize * 2 + 1);
+
+                if previous != Some((upper, lower)) {
+                    queue!(
+                        out,
+                        SetForegroundColor(color(upper)),
+                        SetBackgroundColor(color(lower))
+                    )?;
+                    previous = Some((upper, lower));
+                }
+
+                queue!(out, Print('▀'))?;
+            }
+        }
+
+        let status = match self.goal {
+            _ if self.finished => "finished".to_string(),
+            Goal::Paused => "paused".to_string(),
+            Goal::Playing => format!("playing {} steps a frame", self.speed),
+            _ => "running".to_string(),
+        };
+        let mut status_line = format!(
+            "{title}  step {}  milestones {}  {status}  view {},{} at 1:{}",
+            self.steps, self.milestones, self.view.left, self.view.top, self.view.zoom
+        );
+
+        if let Some(message) = &self.message {
+            status_line.push_str(&format!("  ({message})"));
+        }
+
+        let state_line = self.simulation.state()
+            .iter()
+            .map(|(name, value)| format!("{name}: {value}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+
+        let keys_line = match self.count {
+            Some(count) => format!("{count}_"),
+            None => KEYS_HELP.to_string(),
+        };
+
+        queue!(out, ResetColor)?;
+
+        for (offset, text) in [status_line, state_line, keys_line].iter().enumerate() {
+            let line = rows.saturating_sub(STATUS_LINES) + offset as u16;
+            let text: String = text.chars().take(columns as usize).collect();
+
+            queue!(
+                out,
+                cursor::MoveTo(0, line),
+                Print(text),
+                terminal::Clear(ClearType::UntilNewLine)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts up to its limit, every third step is a milestone.
+    struct Counter {
+        count: usize,
+        limit: usize,
+    }
+
+    impl Simulation for Counter {
+        fn step(&mut self) -> Step {
+            if self.count == self.limit {
+                return Step::Finished;
+            }
+
+            self.count += 1;
+
+            if self.count.is_multiple_of(3) {
+                Step::Milestone
+            } else {
+                Step::Running
+            }
+        }
+
+        fn frame(&self) -> Frame {
+            Frame::from_fn(self.limit, 1, |column, _| {
+                if column < self.count { [255, 255, 255] } else { [0, 0, 0] }
+            })
+        }
+
+        fn state(&self) -> Vec<(&'static str, String)> {
+            vec![("count", self.count.to_string())]
+        }
+    }
+
+    fn press(stepper: &mut Stepper, keys: &str) {
+        for key in keys.chars() {
+            assert!(stepper.handle(KeyCode::Char(key)));
+        }
+
+        while stepper.goal != Goal::Paused {
+            stepper.advance();
+        }
+    }
+
+    #[test]
+    fn test_stepping_goals() {
+        let mut stepper = Stepper::new(Box::new(Counter { count: 0, limit: 20 }), (80, 24));
+
+        press(&mut stepper, "s");
+        assert_eq!(stepper.steps, 1);
+
+        press(&mut stepper, "4s");
+        assert_eq!(stepper.steps, 5);
+
+        press(&mut stepper, "n");
+        assert_eq!((stepper.steps, stepper.milestones), (6, 2));
+
+        press(&mut stepper, "2n");
+        assert_eq!((stepper.steps, stepper.milestones), (12, 4));
+
+        press(&mut stepper, "3g");
+        assert_eq!(stepper.steps, 12);
+        assert_eq!(stepper.message.as_deref(), Some("already past step 3"));
+
+        press(&mut stepper, "15g");
+        assert_eq!(stepper.steps, 15);
+
+        press(&mut stepper, "G");
+        assert!(stepper.finished);
+        assert_eq!(stepper.steps, 20);
+
+        press(&mut stepper, "s");
+        assert_eq!(stepper.steps, 20);
+        assert_eq!(stepper.message.as_deref(), Some("the simulation has finished"));
+
+        assert!(!stepper.handle(KeyCode::Char('q')));
+    }
+
+    #[test]
+    fn test_playing_speed() {
+        let mut stepper = Stepper::new(Box::new(Counter { count: 0, limit: 100 }), (80, 24));
+
+        press(&mut stepper, ">>");
+        stepper.handle(KeyCode::Char(' '));
+        stepper.advance();
+        stepper.advance();
+
+        assert_eq!(stepper.goal, Goal::Playing);
+        assert_eq!(stepper.steps, 8);
+
+        stepper.handle(KeyCode::Char(' '));
+        stepper.advance();
+
+        assert_eq!(stepper.goal, Goal::Paused);
+        assert_eq!(stepper.steps, 8);
+    }
+
+    #[test]
+    fn test_view() {
+        let frame = Frame::from_fn(100, 10, |column, row| {
+            if (column, row) == (37, 5) { [255, 0, 0] } else { [10, 10, 10] }
+        })
+        .with_origin(400, 0);
+
+        let view = View::fit(&frame, 30, 10);
+        assert_eq!(view, View { left: 400, top: 0, zoom: 4 });
+
+        // The zoomed out pixel covering the single bright cell shows it
+        assert_eq!(view.pixel(&frame, 9, 1), Some([255, 0, 0]));
+        assert_eq!(view.pixel(&frame, 8, 1), Some([10, 10, 10]));
+        assert_eq!(view.pixel(&frame, 25, 0), None);
+
+        let zoomed_in = View { zoom: 1, ..view };
+        assert_eq!(zoomed_in.pixel(&frame, 37, 5), Some([255, 0, 0]));
+        assert_eq!(zoomed_in.pixel(&frame, 36, 5), Some([10, 10, 10]));
+    }
+}
@@ -0,0 +1,544 @@
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use aoc_common::{InputSource, Part, Progress};
+use serde::{Deserialize, Serialize};
+
+use crate::client::Client;
+use crate::config::Config;
+use crate::days::Day;
+use crate::params::ParamsFile;
+
+/// Default location of the record of every answer submitted, at the root of the workspace.
+pub const HISTORY_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../.submissions.json");
+
+pub struct SubmitOptions<'a> {
+    pub day: u8,
+    pub part: Part,
+    /// Answer to submit, by default the day is solved and its answer submitted.
+    pub answer: Option<String>,
+    pub input: Option<&'a str>,
+    /// Parameters the day is solved with when there isn't an answer.
+    pub params: &'a ParamsFile,
+    /// Wait out any cooldown instead of giving up.
+    pub wait: bool,
+    pub history_path: &'a Path,
+}
+
+/// What the site made of a submitted answer.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint about which direction.
+    Incorrect,
+    /// The answer wasn't looked at because the last attempt was too recent.
+    TooSoon,
+    /// The part was already solved, or isn't unlocked yet.
+    WrongLevel,
+}
+
+impl Outcome {
+    /// Whether the answer was judged and found to be wrong.
+    fn is_wrong(self) -> bool {
+        matches!(self, Outcome::TooHigh | Outcome::TooLow | Outcome::Incorrect)
+    }
+}
+
+impl std::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let description = match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too high",
+            Outcome::TooLow => "too low",
+            Outcome::Incorrect => "not the right answer",
+            Outcome::TooSoon => "submitted too soon after the last answer",
+            Outcome::WrongLevel => "for a part that is already solved or still locked",
+        };
+
+        write!(f, "{description}")
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Response {
+    pub outcome: Outcome,
+    /// How long the site asked to wait before the next attempt.
+    pub wait: Option<Duration>,
+}
+
+/// One answer that was sent to the site.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Attempt {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Seconds since the unix epoch when the answer was submitted.
+    pub submitted_at: u64,
+    /// Seconds since the unix epoch before which no more answers should be submitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<u64>,
+}
+
+/// Why an answer wasn't submitted.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Refusal {
+    AlreadySolved(String),
+    /// The same answer was already found to be wrong.
+    Repeated(Outcome),
+    /// An earlier answer that was too high or too low rules this one out.
+    RuledOut(Outcome, String),
+    Cooldown(Duration),
+}
+
+impl std::fmt::Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => write!(f, "already solved, the answer was {answer}"),
+            Refusal::Repeated(outcome) => write!(f, "already submitted and it was {outcome}"),
+            Refusal::RuledOut(outcome, previous) => {
+                write!(f, "it must also be {outcome} since {previous} already was")
+            }
+            Refusal::Cooldown(remaining) => {
+                write!(f, "submitted too recently, {}s left to wait", remaining.as_secs())
+            }
+        }
+    }
+}
+
+/// Every answer submitted so far, kept so the same wrong answer is never sent twice and
+/// cooldowns are respected across runs.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct History {
+    attempts: Vec<Attempt>,
+}
+
+impl History {
+    /// Whether an answer should be submitted given the earlier attempts, `now` is in seconds
+    /// since the unix epoch.
+    pub fn check(&self, day: u8, part: Part, answer: &str, now: u64) -> Result<(), Refusal> {
+        let attempts: Vec<&Attempt> = self
+            .attempts
+            .iter()
+            .filter(|a| a.day == day && a.part == part.number())
+            .collect();
+
+        if let Some(correct) = attempts.iter().find(|a| a.outcome == Outcome::Correct) {
+            return Err(Refusal::AlreadySolved(correct.answer.clone()));
+        }
+
+        for attempt in attempts.iter().filter(|a| a.outcome.is_wrong()) {
+            if attempt.answer == answer {
+                return Err(Refusal::Repeated(attempt.outcome));
+            }
+
+            if let (Ok(previous), Ok(current)) = (attempt.answer.parse::<i128>(), answer.parse::<i128>()) {
+                let ruled_out = match attempt.outcome {
+                    Outcome::TooHigh => current >= previous,
+                    Outcome::TooLow => current <= previous,
+                    _ => false,
+                };
+
+                if ruled_out {
+                    return Err(Refusal::RuledOut(attempt.outcome, attempt.answer.clone()));
+                }
+            }
+        }
+
+        let retry_after = attempts.iter().filter_map(|a| a.retry_after).max();
+        if let Some(retry_after) = retry_after.filter(|&r| r > now) {
+            return Err(Refusal::Cooldown(Duration::from_secs(retry_after - now)));
+        }
+
+        Ok(())
+    }
+
+    /// Reads the history, a missing file is an empty history.
+    pub fn load(path: &Path) -> Result<History, String> {
+        let contents = match std::fs::read(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(History::default()),
+            Err(err) => return Err(format!("unable to read submission history {}: {err}", path.display())),
+        };
+
+        serde_json::from_slice(&contents)
+            .map_err(|err| format!("invalid submission history {}: {err}", path.display()))
+    }
+
+    pub fn record(&mut self, attempt: Attempt) {
+        self.attempts.push(attempt);
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let contents = serde_json::to_vec_pretty(self).unwrap();
+
+        std::fs::write(path, contents)
+            .map_err(|err| format!("unable to save submission history {}: {err}", path.display()))
+    }
+}
+
+/// Solves the day, or takes the provided answer, and submits it unless the history shows it would
+/// be pointless or too soon.
+pub fn submit(options: SubmitOptions, config: &Config) -> ExitCode {
+    let SubmitOptions { day, part, .. } = options;
+
+    let answer = match options.answer {
+        Some(answer) => answer,
+        None => match solve(day, part, options.input, options.params) {
+            Ok(answer) => answer,
+            Err(err) => {
+                eprintln!("{err}");
+                return ExitCode::FAILURE;
+            }
+        },
+    };
+
+    let mut history = match History::load(options.history_path) {
+        Ok(history) => history,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let client = match Client::new(&config.client) {
+        Ok(client) => client,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut result = attempt(&client, &mut history, day, part, &answer, now());
+
+    if let Err(AttemptError::Refused(Refusal::Cooldown(remaining))) = &result {
+        if options.wait {
+            eprintln!("waiting {}s before submitting", remaining.as_secs());
+            std::thread::sleep(*remaining);
+
+            result = attempt(&client, &mut history, day, part, &answer, now());
+        }
+    }
+
+    if let Err(err) = history.save(options.history_path) {
+        eprintln!("{err}");
+    }
+
+    match result {
+        Ok(attempt) => {
+            print!("Day {day:02} part {part}: {answer} is {}", attempt.outcome);
+
+            match attempt.retry_after {
+                Some(retry_after) => println!(", wait {}s before trying again", retry_after - attempt.submitted_at),
+                None => println!(),
+            }
+
+            if attempt.outcome == Outcome::Correct {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Err(AttemptError::Refused(refusal)) => {
+            eprintln!("Day {day:02} part {part}: not submitting {answer}, {refusal}");
+            ExitCode::FAILURE
+        }
+        Err(AttemptError::Failed(err)) => {
+            eprintln!("Day {day:02} part {part}: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[derive(Debug)]
+enum AttemptError {
+    Refused(Refusal),
+    Failed(String),
+}
+
+/// Checks the answer against the history, submits it and records the outcome.
+fn attempt(
+    client: &Client,
+    history: &mut History,
+    day: u8,
+    part: Part,
+    answer: &str,
+    now: u64,
+) -> Result<Attempt, AttemptError> {
+    history.check(day, part, answer, now).map_err(AttemptError::Refused)?;
+
+    let page = client.submit(day, part, answer).map_err(AttemptError::Failed)?;
+    let response = parse_response(&page).map_err(AttemptError::Failed)?;
+
+    let attempt = Attempt {
+        day,
+        part: part.number(),
+        answer: answer.to_string(),
+        outcome: response.outcome,
+        submitted_at: now,
+        retry_after: response.wait.map(|wait| now + wait.as_secs()),
+    };
+
+    history.record(attempt.clone());
+
+    Ok(attempt)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Interprets the page returned after submitting an answer. The site only responds with prose so
+/// this looks for the known phrases within the page's article.
+pub fn parse_response(page: &str) -> Result<Response, String> {
+    let text = article_text(page);
+
+    let outcome = if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if text.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Incorrect
+        }
+    } else if text.contains("You gave an answer too recently") {
+        Outcome::TooSoon
+    } else if text.contains("You don't seem to be solving the right level") {
+        Outcome::WrongLevel
+    } else {
+        return Err(format!("unrecognized response: {text}"));
+    };
+
+    let wait = match outcome {
+        Outcome::TooSoon => between(&text, "You have ", " left to wait").and_then(parse_wait_time),
+        _ => between(&text, "wait ", " before trying again").and_then(parse_wait_words),
+    };
+
+    Ok(Response { outcome, wait })
+}
+
+/// The text of the page's `<article>`, which holds the response, with the markup removed.
+fn article_text(page: &str) -> String {
+    let article = page
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(page, |(article, _)| article);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn between<'a>(text: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let (_, rest) = text.split_once(start)?;
+    let (value, _) = rest.split_once(end)?;
+    Some(value)
+}
+
+/// Parses the remaining time given after an early submission, such as `1m 23s`.
+fn parse_wait_time(text: &str) -> Option<Duration> {
+    let mut seconds = 0;
+
+    for component in text.split_whitespace() {
+        let unit_at = component.find(|c: char| !c.is_ascii_digit())?;
+        let (value, unit) = component.split_at(unit_at);
+        let value: u64 = value.parse().ok()?;
+
+        seconds += match unit {
+            "h" => value * 3600,
+            "m" => value * 60,
+            "s" => value,
+            _ => return None,
+        };
+    }
+
+    Some(Duration::from_secs(seconds))
+}
+
+/// Parses the cooldown given after a wrong answer, such as `one minute` or `5 minutes`.
+fn parse_wait_words(text: &str) -> Option<Duration> {
+    let (amount, unit) = text.split_once(' ')?;
+
+    let amount = match amount {
+        "one" => 1,
+        amount => amount.parse().ok()?,
+    };
+
+    match unit.trim_end_matches('s') {
+        "second" => Some(Duration::from_secs(amount)),
+        "minute" => Some(Duration::from_secs(amount * 60)),
+        _ => None,
+    }
+}
+
+/// Solves a single part of the day on its input to get the answer to submit.
+fn solve(
+    day: u8,
+    part: Part,
+    input: Option<&str>,
+    params: &ParamsFile,
+) -> Result<String, String> {
+    let Some(day) = Day::find(day) else {
+        return Err(format!("day {day} doesn't have a solution yet, pass the answer with --answer"));
+    };
+
+    let source = InputSource::from_arg(input, day.data_dir);
+    let data = source.read().map_err(|err| err.to_string())?;
+    let params = params.for_source(day, &source, &[])?;
+
+    let report = params
+        .scope(|| day.run(&data, &[part], &Progress::new(), None, None))
+        .map_err(|err| format!("failed to parse puzzle input {source}\n{}", err.render()))?;
+
+    match report.parts[0].answer.clone() {
+        Some(answer) if answer.contains('\n') => Err(
+            "the answer is rendered as a picture, read it and pass it with --answer".to_string(),
+        ),
+        Some(answer) => Ok(answer),
+        None => Err(format!("day {:02} part {part} doesn't have an answer to submit", day.number)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::client::tests::mock_server;
+
+    fn page(article: &str) -> String {
+        format!("<html><body><main>\n<article><p>{article}</p></article>\n</main></body></html>")
+    }
+
+    fn attempt_record(answer: &str, outcome: Outcome, retry_after: Option<u64>) -> Attempt {
+        Attempt {
+            day: 15,
+            part: 2,
+            answer: answer.to_string(),
+            outcome,
+            submitted_at: 1000,
+            retry_after,
+        }
+    }
+
+    #[test]
+    fn test_parse_responses() {
+        let response = parse_response(&page(
+            "That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to collecting enough star fruit.",
+        ));
+        assert_eq!(response, Ok(Response { outcome: Outcome::Correct, wait: None }));
+
+        let response = parse_response(&page(
+            "That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href=\"/2022/about\">about page</a>, or you can ask for hints on the <a href=\"https://www.reddit.com/r/adventofcode/\" target=\"_blank\">subreddit</a>.  Please wait one minute before trying again. <a href=\"/2022/day/15\">[Return to Day 15]</a>",
+        ));
+        assert_eq!(
+            response,
+            Ok(Response { outcome: Outcome::TooHigh, wait: Some(Duration::from_secs(60)) })
+        );
+
+        let response = parse_response(&page(
+            "That's not the right answer; your answer is too low.  Please wait 5 minutes before trying again.",
+        ));
+        assert_eq!(
+            response,
+            Ok(Response { outcome: Outcome::TooLow, wait: Some(Duration::from_secs(300)) })
+        );
+
+        let response = parse_response(&page(
+            "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait. <a href=\"/2022/day/15\">[Return to Day 15]</a>",
+        ));
+        assert_eq!(
+            response,
+            Ok(Response { outcome: Outcome::TooSoon, wait: Some(Duration::from_secs(83)) })
+        );
+
+        let response = parse_response(&page(
+            "You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2022/day/15\">[Return to Day 15]</a>",
+        ));
+        assert_eq!(response, Ok(Response { outcome: Outcome::WrongLevel, wait: None }));
+
+        let err = parse_response(&page("Something <em>unexpected</em> happened")).unwrap_err();
+        assert_eq!(err, "unrecognized response: Something unexpected happened");
+    }
+
+    #[test]
+    fn test_history_checks() {
+        let mut history = History::default();
+        assert_eq!(history.check(15, Part::Two, "100", 1000), Ok(()));
+
+        history.record(attempt_record("100", Outcome::TooHigh, Some(1060)));
+        history.record(attempt_record("20", Outcome::TooLow, None));
+        history.record(attempt_record("50", Outcome::Incorrect, None));
+
+        assert_eq!(history.check(15, Part::Two, "50", 2000), Err(Refusal::Repeated(Outcome::Incorrect)));
+        assert_eq!(
+            history.check(15, Part::Two, "150", 2000),
+            Err(Refusal::RuledOut(Outcome::TooHigh, "100".to_string()))
+        );
+        assert_eq!(
+            history.check(15, Part::Two, "10", 2000),
+            Err(Refusal::RuledOut(Outcome::TooLow, "20".to_string()))
+        );
+        assert_eq!(
+            history.check(15, Part::Two, "60", 1030),
+            Err(Refusal::Cooldown(Duration::from_secs(30)))
+        );
+        assert_eq!(history.check(15, Part::Two, "60", 1060), Ok(()));
+
+        // Other parts have their own history
+        assert_eq!(history.check(15, Part::One, "50", 1030), Ok(()));
+
+        history.record(attempt_record("60", Outcome::Correct, None));
+        assert_eq!(
+            history.check(15, Part::Two, "70", 2000),
+            Err(Refusal::AlreadySolved("60".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_submission_round_trip() {
+        // The server only answers a single request, the repeated answer must not reach it
+        let (config, server) = mock_server(1, |method, url, cookie, body| {
+            assert_eq!(method, "POST");
+            assert_eq!(url, "/2022/day/15/answer");
+            assert_eq!(cookie, "session=53616c7465645f5f");
+            assert_eq!(body, "level=2&answer=12630143363768");
+
+            (200, page("That's not the right answer; your answer is too high.  Please wait one minute before trying again."))
+        });
+        let client = Client::new(&config).unwrap();
+        let mut history = History::default();
+
+        let recorded = attempt(&client, &mut history, 15, Part::Two, "12630143363768", 1000).unwrap();
+        assert_eq!(recorded, attempt_record("12630143363768", Outcome::TooHigh, Some(1060)));
+
+        let err = attempt(&client, &mut history, 15, Part::Two, "12630143363768", 2000).unwrap_err();
+        assert!(matches!(err, AttemptError::Refused(Refusal::Repeated(Outcome::TooHigh))));
+
+        server.join().unwrap();
+
+        let path = std::env::temp_dir().join(format!("aoc-submissions-test-{}.json", std::process::id()));
+        history.save(&path).unwrap();
+        let reloaded = History::load(&path).unwrap();
+        assert_eq!(reloaded.attempts, history.attempts);
+        std::fs::remove_file(path).unwrap();
+    }
+}
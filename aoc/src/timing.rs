@@ -0,0 +1,177 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use aoc_common::Part;
+use serde::Serialize;
+
+use crate::days::DayReport;
+
+/// Summary of how long a single phase (parsing or one of the parts) took across repeated runs.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub struct Stats {
+    #[serde(rename = "min_ns", serialize_with = "as_nanos")]
+    pub min: Duration,
+    #[serde(rename = "median_ns", serialize_with = "as_nanos")]
+    pub median: Duration,
+    #[serde(rename = "max_ns", serialize_with = "as_nanos")]
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Returns `None` when there are no samples to summarize. With an even number of samples the
+    /// median is the mean of the two middle samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+
+        Some(Stats {
+            min: sorted[0],
+            median,
+            max: sorted[sorted.len() - 1],
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PhaseTiming {
+    pub phase: String,
+    #[serde(flatten)]
+    pub stats: Stats,
+}
+
+/// Timing of every phase of a day across a number of repeated runs.
+#[derive(Debug, Serialize)]
+pub struct TimingReport {
+    pub day: u8,
+    pub runs: usize,
+    /// Seconds since the unix epoch when the report was produced, so reports collected over time
+    /// can be ordered.
+    pub timestamp: u64,
+    pub phases: Vec<PhaseTiming>,
+}
+
+impl TimingReport {
+    /// Collects the timing of each phase from the reports of repeated runs of the same day.
+    pub fn from_runs(day: u8, reports: &[DayReport]) -> TimingReport {
+        let mut phases = vec![];
+
+        let parse_samples: Vec<Duration> = reports.iter().map(|r| r.parse_time).collect();
+        if let Some(stats) = Stats::from_samples(&parse_samples) {
+            phases.push(PhaseTiming {
+                phase: "parse".to_string(),
+                stats,
+            });
+        }
+
+        for part in Part::ALL {
+            let part_samples: Vec<Duration> = reports
+                .iter()
+                .flat_map(|r| r.parts.iter())
+                .filter(|pr| pr.part == part)
+                .map(|pr| pr.elapsed)
+                .collect();
+
+            if let Some(stats) = Stats::from_samples(&part_samples) {
+                phases.push(PhaseTiming {
+                    phase: format!("part_{}", part.number()),
+                    stats,
+                });
+            }
+        }
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        TimingReport {
+            day,
+            runs: reports.len(),
+            timestamp,
+            phases,
+        }
+    }
+
+    pub fn display_table(&self) -> String {
+        let mut output = format!("Day {:02} timing over {} runs\n", self.day, self.runs);
+        output.push_str(&format!(
+            "  {:<8} {:>14} {:>14} {:>14}",
+            "phase", "min", "median", "max"
+        ));
+
+        for phase in &self.phases {
+            output.push_str(&format!(
+                "\n  {:<8} {:>14} {:>14} {:>14}",
+                phase.phase,
+                format!("{:.2?}", phase.stats.min),
+                format!("{:.2?}", phase.stats.median),
+                format!("{:.2?}", phase.stats.max),
+            ));
+        }
+
+        output
+    }
+}
+
+pub fn as_nanos<S: serde::Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(duration.as_nanos().try_into().unwrap_or(u64::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::days::PartReport;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_stats() {
+        assert_eq!(Stats::from_samples(&[]), None);
+
+        let stats = Stats::from_samples(&[ms(5), ms(1), ms(3)]).unwrap();
+        assert_eq!(stats, Stats { min: ms(1), median: ms(3), max: ms(5) });
+
+        let stats = Stats::from_samples(&[ms(4), ms(1), ms(2), ms(8)]).unwrap();
+        assert_eq!(stats, Stats { min: ms(1), median: ms(3), max: ms(8) });
+    }
+
+    #[test]
+    fn test_report_from_runs() {
+        let run = |parse: u64, part_one: u64| DayReport {
+            normalizations: vec![],
+            parse_time: ms(parse),
+            parts: vec![PartReport {
+                part: Part::One,
+                answer: Some("24000".to_string()),
+                elapsed: ms(part_one),
+
+                interrupted: None,
+                cached: false,
+            }],
+        };
+
+        let report = TimingReport::from_runs(1, &[run(2, 10), run(1, 30), run(3, 20)]);
+
+        assert_eq!(report.runs, 3);
+        assert_eq!(report.phases.len(), 2);
+        assert_eq!(report.phases[0].phase, "parse");
+        assert_eq!(report.phases[0].stats.median, ms(2));
+        assert_eq!(report.phases[1].phase, "part_1");
+        assert_eq!(report.phases[1].stats.max, ms(30));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["phases"][1]["min_ns"], 10_000_000);
+    }
+}
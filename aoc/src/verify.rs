@@ -0,0 +1,106 @@
+use std::path::Path;
+use std::process::ExitCode;
+
+use aoc_common::{display_answer, Part, Progress};
+
+use crate::answers::{Answers, Verdict};
+use crate::days::{Day, DAYS};
+use crate::params::ParamsFile;
+
+/// Solves every input in the data directory of the selected days (or all of them) and compares the
+/// answers against the known correct ones. Any mismatch is treated as a regression and results in
+/// a failing exit code, answers that aren't known yet are only reported.
+///
+/// Each input is solved with its own parameters from the parameters file.
+pub fn verify(day: Option<u8>, answers_path: &Path, params: &ParamsFile) -> ExitCode {
+    let answers = match Answers::load(answers_path) {
+        Ok(answers) => answers,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let days: Vec<&Day> = match day {
+        Some(number) => match Day::find(number) {
+            Some(day) => vec![day],
+            None => {
+                eprintln!("day {number} doesn't have a solution yet");
+                return ExitCode::FAILURE;
+            }
+        },
+        None => DAYS.iter().collect(),
+    };
+
+    let (mut passed, mut failed, mut unknown) = (0, 0, 0);
+
+    for day in days {
+        let inputs = match day.inputs() {
+            Ok(inputs) => inputs,
+            Err(err) => {
+                eprintln!("Day {:02}: unable to list inputs: {err}", day.number);
+                failed += 1;
+                continue;
+            }
+        };
+
+        for (input_name, path) in inputs {
+            let label = format!("Day {:02} {input_name:<8}", day.number);
+
+            let report = std::fs::read(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|data| {
+                    let params = params.for_day(day, Some(&input_name), &[])?;
+
+                    params
+                        .scope(|| day.run(&data, &Part::ALL, &Progress::new(), None, None))
+                        .map_err(|err| err.to_string())
+                });
+
+            let report = match report {
+                Ok(report) => report,
+                Err(err) => {
+                    println!("{label} FAIL: {err}");
+                    failed += 1;
+                    continue;
+                }
+            };
+
+            for part_report in report.parts {
+                let answer = part_report.answer.as_deref();
+
+                match answers.verify(day.number, &input_name, part_report.part, answer) {
+                    Verdict::Pass => {
+                        println!("{label} part {}: pass", part_report.part);
+                        passed += 1;
+                    }
+                    Verdict::Fail(expected) => {
+                        println!(
+                            "{label} part {}: FAIL expected {}, got {}",
+                            part_report.part,
+                            display_answer(Some(&expected)),
+                            display_answer(answer),
+                        );
+                        failed += 1;
+                    }
+                    Verdict::Unknown => {
+                        println!(
+                            "{label} part {}: unknown, got {}",
+                            part_report.part,
+                            display_answer(answer)
+                        );
+                        unknown += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    println!("{passed} passed, {failed} failed, {unknown} unknown");
+
+    if failed > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -0,0 +1,196 @@
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+thread_local! {
+    static CURRENT: RefCell<Option<Checkpoint>> = const { RefCell::new(None) };
+}
+
+/// Returns the checkpoint installed for the solution running on this thread by
+/// [`Checkpoint::scope`], if checkpointing was requested.
+pub fn current() -> Option<Checkpoint> {
+    CURRENT.with(|c| c.borrow().clone())
+}
+
+/// A file long running solvers can periodically save their state into, and resume from on a later
+/// run. The runner is responsible for picking a path that is unique to the day, part and input so
+/// a solver never resumes from state that belongs to different work.
+///
+/// Failing to save a checkpoint is reported but never stops the solver, losing a checkpoint only
+/// costs time.
+#[derive(Clone)]
+pub struct Checkpoint {
+    inner: Arc<CheckpointState>,
+}
+
+struct CheckpointState {
+    path: PathBuf,
+    interval: Duration,
+    last_saved: Mutex<Instant>,
+}
+
+impl Checkpoint {
+    /// Removes the checkpoint, this should be done once the solver has finished its work.
+    pub fn clear(&self) {
+        if let Err(err) = std::fs::remove_file(self.path()) {
+            if err.kind() != std::io::ErrorKind::NotFound {
+                tracing::warn!(path = %self.path().display(), %err, "failed to remove checkpoint");
+            }
+        }
+    }
+
+    pub fn exists(&self) -> bool {
+        self.path().exists()
+    }
+
+    /// Loads the previously saved state. A checkpoint that can't be read or no longer matches
+    /// the shape of the state is ignored so the solver starts over.
+    pub fn load<T: DeserializeOwned>(&self) -> Option<T> {
+        let contents = std::fs::read(self.path()).ok()?;
+
+        match serde_json::from_slice(&contents) {
+            Ok(state) => Some(state),
+            Err(err) => {
+                tracing::warn!(path = %self.path().display(), %err, "ignoring unusable checkpoint");
+                None
+            }
+        }
+    }
+
+    /// Creates a checkpoint that will be saved at most once per interval by
+    /// [`Checkpoint::save_if_due`].
+    pub fn new(path: impl Into<PathBuf>, interval: Duration) -> Self {
+        Checkpoint {
+            inner: Arc::new(CheckpointState {
+                path: path.into(),
+                interval,
+                last_saved: Mutex::new(Instant::now()),
+            }),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.inner.path
+    }
+
+    /// Writes the state to the checkpoint file. The state is written to a temporary file first and
+    /// moved into place so an interrupted save never corrupts the previous checkpoint.
+    pub fn save<T: Serialize>(&self, state: &T) {
+        *self.inner.last_saved.lock().unwrap() = Instant::now();
+
+        if let Err(err) = self.write(state) {
+            tracing::warn!(path = %self.path().display(), %err, "failed to save checkpoint");
+        }
+    }
+
+    /// Saves the state produced by the closure if the interval has passed since the last save.
+    /// The closure is only called when a save is due so expensive state can be built lazily.
+    pub fn save_if_due<T: Serialize>(&self, state: impl FnOnce() -> T) {
+        let due = self.inner.last_saved.lock().unwrap().elapsed() >= self.inner.interval;
+
+        if due {
+            self.save(&state());
+        }
+    }
+
+    /// Installs this checkpoint as the [`current`] one for the duration of the closure.
+    pub fn scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        struct Restore(Option<Checkpoint>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let previous = self.0.take();
+                CURRENT.with(|c| *c.borrow_mut() = previous);
+            }
+        }
+
+        let previous = CURRENT.with(|c| c.borrow_mut().replace(self.clone()));
+        let _restore = Restore(previous);
+
+        f()
+    }
+
+    fn write<T: Serialize>(&self, state: &T) -> std::io::Result<()> {
+        if let Some(parent) = self.path().parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut tmp_path = self.path().as_os_str().to_owned();
+        tmp_path.push(".tmp");
+
+        std::fs::write(&tmp_path, serde_json::to_vec(state)?)?;
+        std::fs::rename(&tmp_path, self.path())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+    struct SearchState {
+        next_row: isize,
+    }
+
+    fn test_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("aoc-checkpoint-test-{}", std::process::id()))
+            .join(name)
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let checkpoint = Checkpoint::new(test_path("round_trip.json"), Duration::ZERO);
+        assert_eq!(checkpoint.load::<SearchState>(), None);
+
+        checkpoint.save(&SearchState { next_row: 42 });
+        assert!(checkpoint.exists());
+        assert_eq!(checkpoint.load(), Some(SearchState { next_row: 42 }));
+
+        checkpoint.clear();
+        assert!(!checkpoint.exists());
+        assert_eq!(checkpoint.load::<SearchState>(), None);
+    }
+
+    #[test]
+    fn test_saving_interval() {
+        let checkpoint = Checkpoint::new(test_path("interval.json"), Duration::from_secs(3600));
+
+        checkpoint.save_if_due(|| SearchState { next_row: 1 });
+        assert!(!checkpoint.exists());
+
+        checkpoint.save(&SearchState { next_row: 2 });
+        checkpoint.save_if_due(|| SearchState { next_row: 3 });
+        assert_eq!(checkpoint.load(), Some(SearchState { next_row: 2 }));
+
+        checkpoint.clear();
+    }
+
+    #[test]
+    fn test_mismatched_state_is_ignored() {
+        let checkpoint = Checkpoint::new(test_path("mismatched.json"), Duration::ZERO);
+
+        checkpoint.save(&vec![1, 2, 3]);
+        assert_eq!(checkpoint.load::<SearchState>(), None);
+
+        checkpoint.clear();
+    }
+
+    #[test]
+    fn test_scoped_current_checkpoint() {
+        let checkpoint = Checkpoint::new(test_path("scoped.json"), Duration::ZERO);
+
+        assert!(current().is_none());
+        assert_eq!(
+            checkpoint.scope(|| current().map(|c| c.path().to_path_buf())),
+            Some(test_path("scoped.json"))
+        );
+        assert!(current().is_none());
+    }
+}
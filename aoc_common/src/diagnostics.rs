@@ -0,0 +1,278 @@
+use std::io::BufRead;
+use std::ops::Range;
+
+use crate::normalize::{normalize_line, LineChanges};
+
+/// A problem found while parsing puzzle input, pointing at the exact piece of the offending line
+/// so it can be shown to whoever supplied the input instead of panicking.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    /// One-indexed line number, zero until the error has been placed within the whole input.
+    line_number: usize,
+
+    /// Byte range of the offending text within the line.
+    span: Range<usize>,
+    line: String,
+
+    message: String,
+}
+
+impl ParseError {
+    /// Creates an error highlighting `fragment`, which must be a slice borrowed from `line` (such
+    /// as a piece returned by `split`). This lets line parsers report precise locations without
+    /// tracking offsets by hand.
+    pub fn at(line: &str, fragment: &str, message: impl Into<String>) -> Self {
+        let start = (fragment.as_ptr() as usize).saturating_sub(line.as_ptr() as usize);
+        let start = start.min(line.len());
+        let end = (start + fragment.len()).min(line.len());
+
+        Self::spanning(line, start..end, message)
+    }
+
+    /// Creates an error highlighting everything from the start of `first` through the end of
+    /// `last`, both of which must be slices borrowed from `line`.
+    pub fn between(line: &str, first: &str, last: &str, message: impl Into<String>) -> Self {
+        let start = ParseError::at(line, first, "").span.start;
+        let end = ParseError::at(line, last, "").span.end;
+
+        Self::spanning(line, start..end.max(start), message)
+    }
+
+    /// Creates an error highlighting a byte range of the line.
+    pub fn spanning(line: &str, span: Range<usize>, message: impl Into<String>) -> Self {
+        ParseError {
+            line_number: 0,
+
+            span,
+            line: line.to_string(),
+
+            message: message.into(),
+        }
+    }
+
+    pub fn whole_line(line: &str, message: impl Into<String>) -> Self {
+        Self::spanning(line, 0..line.len(), message)
+    }
+
+    /// One-indexed range of the columns the error covers.
+    pub fn columns(&self) -> Range<usize> {
+        let start = self.char_column(self.span.start);
+        let end = self.char_column(self.span.end).max(start + 1);
+
+        (start + 1)..(end + 1)
+    }
+
+    pub fn line(&self) -> &str {
+        &self.line
+    }
+
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Renders the error along with the offending line and the problematic portion of it
+    /// underlined, in the style of the compiler's own diagnostics.
+    pub fn render(&self) -> String {
+        let columns = self.columns();
+        let gutter = self.line_number.to_string().len();
+
+        let mut output = format!("error: {}\n", self.message);
+        output.push_str(&format!(
+            "{:gutter$}--> line {}, column {}\n",
+            "", self.line_number, columns.start
+        ));
+        output.push_str(&format!("{:gutter$} |\n", ""));
+        output.push_str(&format!("{} | {}\n", self.line_number, self.line));
+        output.push_str(&format!(
+            "{:gutter$} | {}{}",
+            "",
+            " ".repeat(columns.start - 1),
+            "^".repeat(columns.len())
+        ));
+
+        output
+    }
+
+    /// Places an error produced by a line parser at its position within the whole input.
+    pub fn with_line_number(mut self, line_number: usize) -> Self {
+        self.line_number = line_number;
+        self
+    }
+
+    fn char_column(&self, byte_offset: usize) -> usize {
+        self.line
+            .get(..byte_offset)
+            .map(|prefix| prefix.chars().count())
+            .unwrap_or(byte_offset)
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line_number,
+            self.columns().start,
+            self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Interprets the raw input as text, reporting the location of the first invalid byte if it
+/// isn't valid UTF-8.
+pub fn input_str(data: &[u8]) -> Result<&str, ParseError> {
+    std::str::from_utf8(data).map_err(|err| {
+        let valid = &data[..err.valid_up_to()];
+
+        let line_start = valid.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+        let line_end = data[line_start..]
+            .iter()
+            .position(|b| *b == b'\n')
+            .map_or(data.len(), |i| line_start + i);
+
+        let line_number = valid.iter().filter(|b| **b == b'\n').count() + 1;
+        let column = err.valid_up_to() - line_start;
+
+        // The offending line is shown lossily, invalid bytes become a single replacement
+        // character which keeps the caret lined up with the first one.
+        let line = String::from_utf8_lossy(&data[line_start..line_end]);
+
+        ParseError::spanning(&line, column..column + 1, "input is not valid UTF-8")
+            .with_line_number(line_number)
+    })
+}
+
+/// Calls the parser with every line read from the reader in turn, stopping at the first line that
+/// fails. Lines are read into a reused buffer so memory use is bounded by the longest line rather
+/// than the size of the input, which lets parsers work through inputs too large to load at once.
+/// Each line is [normalized](crate::normalize) before it's parsed.
+pub fn for_each_line<R: BufRead>(
+    mut reader: R,
+    mut parser: impl FnMut(&str) -> Result<(), ParseError>,
+) -> Result<(), ParseError> {
+    let mut buffer = vec![];
+    let mut line_number = 0;
+
+    loop {
+        buffer.clear();
+
+        let read = reader.read_until(b'\n', &mut buffer).map_err(|err| {
+            ParseError::whole_line("", format!("failed to read input: {err}"))
+                .with_line_number(line_number + 1)
+        })?;
+
+        if read == 0 {
+            return Ok(());
+        }
+
+        line_number += 1;
+
+        if buffer.ends_with(b"\n") {
+            buffer.pop();
+        }
+
+        // Streamed lines get the same normalization as input that is read up front
+        let line = normalize_line(&buffer, line_number == 1, &mut LineChanges::default());
+        let line = input_str(line).map_err(|err| err.with_line_number(line_number))?;
+        parser(line).map_err(|err| err.with_line_number(line_number))?;
+    }
+}
+
+/// Runs a parser over every line of the input, collecting the results or stopping at the first
+/// line that fails to parse. Puzzle input already in memory can be passed directly as `&[u8]`.
+pub fn parse_lines<R: BufRead, T>(
+    reader: R,
+    mut parser: impl FnMut(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    let mut parsed = vec![];
+
+    for_each_line(reader, |line| {
+        parsed.push(parser(line)?);
+        Ok(())
+    })?;
+
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fragment_columns() {
+        let line = "addx 3x";
+        let err = ParseError::at(line, &line[5..], "invalid number").with_line_number(12);
+
+        assert_eq!(err.columns(), 6..8);
+        assert_eq!(err.to_string(), "line 12, column 6: invalid number");
+    }
+
+    #[test]
+    fn test_rendering() {
+        let line = "A Q";
+        let err = ParseError::at(line, &line[2..], "invalid response \"Q\"").with_line_number(3);
+
+        let expected = "error: invalid response \"Q\"\n \
+                        --> line 3, column 3\n  \
+                        |\n\
+                        3 | A Q\n  \
+                        |   ^";
+        assert_eq!(err.render(), expected);
+    }
+
+    #[test]
+    fn test_empty_fragment_still_underlined() {
+        let line = "noop";
+        let err = ParseError::spanning(line, 4..4, "missing operand").with_line_number(1);
+
+        assert_eq!(err.columns(), 5..6);
+        assert!(err.render().ends_with("|     ^"));
+    }
+
+    #[test]
+    fn test_parse_lines_numbering() {
+        let result = parse_lines(&b"1\n2\nthree\n4"[..], |line| {
+            line.parse::<usize>()
+                .map_err(|_| ParseError::whole_line(line, "not a number"))
+        });
+
+        let err = result.unwrap_err();
+        assert_eq!(err.line_number(), 3);
+        assert_eq!(err.line(), "three");
+        assert_eq!(err.columns(), 1..6);
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let err = input_str(b"ok\nab\xffcd\n").unwrap_err();
+
+        assert_eq!(err.line_number(), 2);
+        assert_eq!(err.columns(), 3..4);
+    }
+
+    #[test]
+    fn test_streamed_lines() {
+        // A tiny buffer makes sure lines are reassembled across reads
+        let reader = std::io::BufReader::with_capacity(2, &b"\xef\xbb\xbfaddx 3\r\nnoop  \n\naddx -5"[..]);
+
+        let mut lines = vec![];
+        for_each_line(reader, |line| {
+            lines.push(line.to_string());
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(lines, vec!["addx 3", "noop", "", "addx -5"]);
+
+        let err = for_each_line(&b"ok\nfine\nab\xffcd\n"[..], |_| Ok(())).unwrap_err();
+        assert_eq!(err.line_number(), 3);
+        assert_eq!(err.columns(), 3..4);
+    }
+}
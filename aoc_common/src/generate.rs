@@ -0,0 +1,33 @@
+//! Support for generating random puzzle inputs, used to stress test and benchmark the parsers and
+//! solvers on inputs much larger (or smaller) than the real ones.
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+pub use rand::Rng;
+
+/// The random number generator every input generator draws from. ChaCha produces the same values
+/// for a seed on every platform and across `rand` releases, so a seed is enough to reproduce an
+/// input.
+pub type InputRng = ChaCha8Rng;
+
+pub fn seeded(seed: u64) -> InputRng {
+    InputRng::seed_from_u64(seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeds_are_reproducible() {
+        let (mut first, mut second, mut other) = (seeded(7), seeded(7), seeded(8));
+
+        let first: Vec<u32> = (0..4).map(|_| first.gen()).collect();
+        let second: Vec<u32> = (0..4).map(|_| second.gen()).collect();
+        let other: Vec<u32> = (0..4).map(|_| other.gen()).collect();
+
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+    }
+}
@@ -0,0 +1,143 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+/// Where a day's puzzle input is read from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InputSource {
+    /// The input that lives alongside the day's crate in its data directory.
+    Bundled(PathBuf),
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Interprets an optional command line argument as an input source. No argument falls back to
+    /// the input in the provided data directory while `-` reads from stdin.
+    pub fn from_arg(arg: Option<&str>, data_dir: &str) -> Self {
+        match arg {
+            None => InputSource::Bundled(Path::new(data_dir).join("input")),
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::File(PathBuf::from(path)),
+        }
+    }
+
+    pub fn read(&self) -> Result<Vec<u8>, InputError> {
+        let path = match self {
+            InputSource::Bundled(path) | InputSource::File(path) => path,
+            InputSource::Stdin => {
+                let mut data = vec![];
+                std::io::stdin()
+                    .read_to_end(&mut data)
+                    .map_err(|err| InputError::Io(self.clone(), err))?;
+
+                return Ok(data);
+            }
+        };
+
+        std::fs::read(path).map_err(|err| match err.kind() {
+            std::io::ErrorKind::NotFound => InputError::Missing(self.clone()),
+            _ => InputError::Io(self.clone(), err),
+        })
+    }
+}
+
+impl std::fmt::Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Bundled(path) | InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Missing(InputSource),
+    Io(InputSource, std::io::Error),
+}
+
+impl std::fmt::Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Missing(InputSource::Bundled(path)) => write!(
+                f,
+                "no puzzle input was provided and there isn't one at {}, either place your input \
+                 there or pass the path to it",
+                path.display()
+            ),
+            InputError::Missing(source) => write!(f, "puzzle input {source} doesn't exist"),
+            InputError::Io(source, err) => write!(f, "failed to read puzzle input {source}: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::Missing(_) => None,
+            InputError::Io(_, err) => Some(err),
+        }
+    }
+}
+
+/// Stable identifier for a puzzle input, used to tell whether saved state was produced from the
+/// same input. This is the hex encoded SHA-256 digest of the raw bytes.
+pub fn content_hash(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_from_arg() {
+        assert_eq!(
+            InputSource::from_arg(None, "day_01/data"),
+            InputSource::Bundled(PathBuf::from("day_01/data/input"))
+        );
+        assert_eq!(InputSource::from_arg(Some("-"), "day_01/data"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg(Some("other/input"), "day_01/data"),
+            InputSource::File(PathBuf::from("other/input"))
+        );
+    }
+
+    #[test]
+    fn test_reading_files() {
+        let path = std::env::temp_dir().join(format!("aoc-input-test-{}", std::process::id()));
+        std::fs::write(&path, b"1000\n2000\n").unwrap();
+
+        let source = InputSource::File(path.clone());
+        assert_eq!(source.read().unwrap(), b"1000\n2000\n");
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(source.read(), Err(InputError::Missing(_))));
+    }
+
+    #[test]
+    fn test_missing_bundled_input_message() {
+        let source = InputSource::from_arg(None, "/nonexistent/day_03/data");
+        let err = source.read().unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "no puzzle input was provided and there isn't one at /nonexistent/day_03/data/input, \
+             either place your input there or pass the path to it"
+        );
+    }
+
+    #[test]
+    fn test_content_hash() {
+        assert_eq!(
+            content_hash(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_ne!(content_hash(b"noop\n"), content_hash(b"noop\r\n"));
+    }
+}
@@ -0,0 +1,21 @@
+pub mod checkpoint;
+pub mod diagnostics;
+pub mod generate;
+mod input;
+pub mod normalize;
+pub mod params;
+pub mod progress;
+pub mod recorder;
+pub mod simulation;
+pub mod snapshot;
+mod solution;
+
+pub use checkpoint::Checkpoint;
+pub use diagnostics::ParseError;
+pub use input::{content_hash, InputError, InputSource};
+pub use normalize::{normalize, Normalization};
+pub use params::{NoParams, Params};
+pub use progress::{Interrupted, Progress};
+pub use recorder::{Frame, FrameSink, Recorder};
+pub use simulation::{Simulation, Step};
+pub use solution::{display_answer, run, Part, Solution};
@@ -0,0 +1,179 @@
+use std::borrow::Cow;
+
+const BYTE_ORDER_MARK: &[u8] = b"\xef\xbb\xbf";
+
+/// A change made to puzzle input before it was parsed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Normalization {
+    ByteOrderMark,
+    CrlfLineEndings { lines: usize },
+    TrailingWhitespace { lines: usize },
+}
+
+impl std::fmt::Display for Normalization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Normalization::ByteOrderMark => write!(f, "removed the byte order mark"),
+            Normalization::CrlfLineEndings { lines } => {
+                write!(f, "converted {lines} CRLF line endings to LF")
+            }
+            Normalization::TrailingWhitespace { lines } => {
+                write!(f, "trimmed trailing whitespace from {lines} lines")
+            }
+        }
+    }
+}
+
+/// Puzzle input after normalization, along with what had to be changed.
+#[derive(Debug)]
+pub struct NormalizedInput<'a> {
+    pub data: Cow<'a, [u8]>,
+    pub changes: Vec<Normalization>,
+}
+
+/// Counts of each change made while normalizing line by line.
+#[derive(Debug, Default)]
+pub(crate) struct LineChanges {
+    byte_order_mark: bool,
+    crlf_lines: usize,
+    trailing_whitespace_lines: usize,
+}
+
+impl LineChanges {
+    fn into_changes(self) -> Vec<Normalization> {
+        let mut changes = vec![];
+
+        if self.byte_order_mark {
+            changes.push(Normalization::ByteOrderMark);
+        }
+
+        if self.crlf_lines > 0 {
+            changes.push(Normalization::CrlfLineEndings {
+                lines: self.crlf_lines,
+            });
+        }
+
+        if self.trailing_whitespace_lines > 0 {
+            changes.push(Normalization::TrailingWhitespace {
+                lines: self.trailing_whitespace_lines,
+            });
+        }
+
+        changes
+    }
+}
+
+/// Cleans up the differences inputs pick up from being edited or copied on other machines, so
+/// parsers only ever see one form: the byte order mark is removed, CRLF line endings become LF and
+/// trailing whitespace is trimmed from every line. Input that is already clean is not copied.
+pub fn normalize(data: &[u8]) -> NormalizedInput<'_> {
+    let mut changes = LineChanges::default();
+    // Only allocated once a line needs changing, everything before it is copied over as is
+    let mut normalized: Option<Vec<u8>> = None;
+    let mut line_start = 0;
+
+    for (idx, line) in data.split_inclusive(|b| *b == b'\n').enumerate() {
+        let has_newline = line.ends_with(b"\n");
+        let content = if has_newline { &line[..line.len() - 1] } else { line };
+        let cleaned = normalize_line(content, idx == 0, &mut changes);
+
+        if normalized.is_none() && cleaned.len() != content.len() {
+            normalized = Some(data[..line_start].to_vec());
+        }
+
+        if let Some(normalized) = &mut normalized {
+            normalized.extend_from_slice(cleaned);
+
+            if has_newline {
+                normalized.push(b'\n');
+            }
+        }
+
+        line_start += line.len();
+    }
+
+    let data = match normalized {
+        Some(normalized) => Cow::Owned(normalized),
+        None => Cow::Borrowed(data),
+    };
+
+    NormalizedInput { data, changes: changes.into_changes() }
+}
+
+/// Normalizes a single line with its `\n` already removed, recording what was changed. This is
+/// shared with the streaming parsers so both see the same input.
+pub(crate) fn normalize_line<'a>(
+    mut line: &'a [u8],
+    first_line: bool,
+    changes: &mut LineChanges,
+) -> &'a [u8] {
+    if first_line {
+        if let Some(rest) = line.strip_prefix(BYTE_ORDER_MARK) {
+            line = rest;
+            changes.byte_order_mark = true;
+        }
+    }
+
+    if let Some(rest) = line.strip_suffix(b"\r") {
+        line = rest;
+        changes.crlf_lines += 1;
+    }
+
+    let trimmed_len = line
+        .iter()
+        .rposition(|b| !b.is_ascii_whitespace())
+        .map_or(0, |i| i + 1);
+
+    if trimmed_len < line.len() {
+        line = &line[..trimmed_len];
+        changes.trailing_whitespace_lines += 1;
+    }
+
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_input_is_borrowed() {
+        let normalized = normalize(b"498,4 -> 498,6\n503,4 -> 502,4\n");
+
+        assert!(matches!(normalized.data, Cow::Borrowed(_)));
+        assert!(normalized.changes.is_empty());
+    }
+
+    #[test]
+    fn test_normalizations() {
+        let normalized = normalize(b"\xef\xbb\xbfA Y\r\nB X  \r\n \t\r\nC Z");
+
+        assert_eq!(&*normalized.data, b"A Y\nB X\n\nC Z");
+        assert_eq!(
+            normalized.changes,
+            vec![
+                Normalization::ByteOrderMark,
+                Normalization::CrlfLineEndings { lines: 3 },
+                Normalization::TrailingWhitespace { lines: 2 },
+            ]
+        );
+        assert_eq!(
+            normalized.changes[1].to_string(),
+            "converted 3 CRLF line endings to LF"
+        );
+    }
+
+    #[test]
+    fn test_line_numbers_are_kept() {
+        let normalized = normalize(b"1000\r\n\r\n2000   \r\n");
+        assert_eq!(&*normalized.data, b"1000\n\n2000\n");
+    }
+
+    #[test]
+    fn test_clean_lines_before_a_change_are_kept() {
+        let normalized = normalize(b"A Y\nB X\nC Z \nA X");
+
+        assert_eq!(&*normalized.data, b"A Y\nB X\nC Z\nA X");
+        assert_eq!(normalized.changes, vec![Normalization::TrailingWhitespace { lines: 1 }]);
+    }
+}
@@ -0,0 +1,130 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+thread_local! {
+    static CURRENT: RefCell<Option<Params>> = const { RefCell::new(None) };
+}
+
+/// Returns the parameters installed for the solution running on this thread by
+/// [`Params::scope`], as the day's parameter type. Outside of a scope, or when none of the
+/// parameters were set, this is the type's default so solvers can always call it.
+///
+/// The runner checks the parameters against the day's [`Solution::Params`](crate::Solution::Params)
+/// before installing them, so this only panics when asked for a different type than the day
+/// declared.
+pub fn current<T: DeserializeOwned + Default>() -> T {
+    Params::current().parse().unwrap_or_else(|err| panic!("invalid puzzle parameters, {err}"))
+}
+
+/// Values for the parameters of a day's puzzle by name, such as the row day 15 looks at. Days
+/// declare the parameters they take as a struct with a default for each of them, only the
+/// parameters that differ from the defaults need values.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct Params {
+    values: BTreeMap<String, Value>,
+}
+
+impl Params {
+    /// The parameters installed by [`Params::scope`] on this thread, none outside of a scope.
+    pub fn current() -> Params {
+        CURRENT.with(|c| c.borrow().clone()).unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn new(values: impl IntoIterator<Item = (String, Value)>) -> Self {
+        Params {
+            values: values.into_iter().collect(),
+        }
+    }
+
+    /// Fills in the parameters of type `T`, any parameter without a value keeps its default.
+    /// Fails when a value has the wrong type or isn't one of the parameters of `T`.
+    pub fn parse<T: DeserializeOwned + Default>(&self) -> Result<T, String> {
+        if self.values.is_empty() {
+            return Ok(T::default());
+        }
+
+        let values = self.values.clone().into_iter().collect();
+
+        serde_json::from_value(Value::Object(values)).map_err(|err| err.to_string())
+    }
+
+    /// Installs these parameters as the [`current`] ones for the duration of the closure.
+    pub fn scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        struct Restore(Option<Params>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let previous = self.0.take();
+                CURRENT.with(|c| *c.borrow_mut() = previous);
+            }
+        }
+
+        let previous = CURRENT.with(|c| c.borrow_mut().replace(self.clone()));
+        let _restore = Restore(previous);
+
+        f()
+    }
+}
+
+/// The parameters of a day that doesn't take any, so any value given is rejected.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NoParams {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(default, deny_unknown_fields)]
+    struct RowParams {
+        row: isize,
+        width: usize,
+    }
+
+    impl Default for RowParams {
+        fn default() -> Self {
+            RowParams {
+                row: 2_000_000,
+                width: 40,
+            }
+        }
+    }
+
+    #[test]
+    fn test_defaults_and_overrides() {
+        assert_eq!(Params::default().parse::<RowParams>(), Ok(RowParams::default()));
+
+        let params = Params::new([("row".to_string(), Value::from(10))]);
+        assert_eq!(params.parse::<RowParams>(), Ok(RowParams { row: 10, width: 40 }));
+
+        let params = Params::new([("rows".to_string(), Value::from(10))]);
+        assert!(params.parse::<RowParams>().unwrap_err().contains("unknown field `rows`"));
+
+        let params = Params::new([("width".to_string(), Value::from(-4))]);
+        assert!(params.parse::<RowParams>().is_err());
+
+        assert!(params.parse::<NoParams>().is_err());
+        assert!(Params::default().parse::<NoParams>().is_ok());
+    }
+
+    #[test]
+    fn test_scoped_params() {
+        assert_eq!(current::<RowParams>().row, 2_000_000);
+
+        let params = Params::new([("row".to_string(), Value::from(10))]);
+        let row = params.scope(|| current::<RowParams>().row);
+
+        assert_eq!(row, 10);
+        assert_eq!(current::<RowParams>().row, 2_000_000);
+    }
+}
@@ -0,0 +1,395 @@
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+thread_local! {
+    static CURRENT: RefCell<Option<Progress>> = const { RefCell::new(None) };
+}
+
+/// Returns the progress handle installed for the solution running on this thread by
+/// [`Progress::scope`]. Outside of a scope this is a handle that never reports anything and is
+/// never interrupted, so solvers can always poll it.
+pub fn current() -> Progress {
+    CURRENT.with(|c| c.borrow().clone()).unwrap_or_default()
+}
+
+type Reporter = Box<dyn Fn(&ProgressUpdate) + Send + Sync>;
+
+/// Shared handle long running solvers periodically poll. Polling hands the handle the solver's
+/// latest progress, which gets passed along to a reporter at a fixed interval, and lets the
+/// solver know whether it should give up early because it was cancelled or ran out of time.
+#[derive(Clone, Default)]
+pub struct Progress {
+    inner: Arc<ProgressState>,
+}
+
+#[derive(Default)]
+struct ProgressState {
+    cancelled: AtomicBool,
+    timeout: Option<Duration>,
+
+    reporter: Option<(Duration, Reporter)>,
+    tracking: Mutex<Tracking>,
+}
+
+#[derive(Default)]
+struct Tracking {
+    latest: Option<ProgressUpdate>,
+    phase_started: Option<Instant>,
+    /// How much was already done when the phase started, solvers resuming from a checkpoint don't
+    /// start from zero.
+    phase_start_done: u64,
+    last_report: Option<Instant>,
+    /// When the phase times out, armed as each scope starts.
+    deadline: Option<Instant>,
+    stop_reason: Option<StopReason>,
+}
+
+impl Progress {
+    /// Requests that any solver polling this handle stops at its next opportunity. This is safe
+    /// to call from other threads, such as a Ctrl-C handler.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Details of why and where the solver was stopped, if it was.
+    pub fn interruption(&self) -> Option<Interrupted> {
+        let tracking = self.inner.tracking.lock().unwrap();
+
+        tracking.stop_reason.map(|reason| Interrupted {
+            reason,
+            progress: tracking.latest.clone(),
+        })
+    }
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Installs this handle as the [`current`] one for the duration of the closure, this is how
+    /// the runner hands the handle to solvers without every solution needing to accept it. The
+    /// progress of any previous phase is forgotten and the timeout starts over, as does whether
+    /// the previous phase timed out.
+    pub fn scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        struct Restore(Option<Progress>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let previous = self.0.take();
+                CURRENT.with(|c| *c.borrow_mut() = previous);
+            }
+        }
+
+        {
+            let mut tracking = self.inner.tracking.lock().unwrap();
+            tracking.latest = None;
+            tracking.phase_started = None;
+            tracking.deadline = self.inner.timeout.map(|timeout| Instant::now() + timeout);
+            tracking.stop_reason = None;
+        }
+
+        let previous = CURRENT.with(|c| c.borrow_mut().replace(self.clone()));
+        let _restore = Restore(previous);
+
+        f()
+    }
+
+    /// Whether the solver should stop, either because the handle was cancelled or the timeout has
+    /// passed.
+    pub fn should_stop(&self) -> bool {
+        let deadline = self.inner.tracking.lock().unwrap().deadline;
+        self.stop_reason(deadline).is_some()
+    }
+
+    /// Records how far along the solver is and reports it if enough time has passed since the
+    /// last report. The label describes what is being counted, such as "rows scanned". When the
+    /// solver should stop this returns the interruption, which is expected to be propagated.
+    pub fn update(&self, label: &'static str, done: u64, total: Option<u64>) -> Result<(), Interrupted> {
+        let now = Instant::now();
+        let mut tracking = self.inner.tracking.lock().unwrap();
+
+        let (phase_started, phase_start_done) = match &tracking.latest {
+            Some(latest) if latest.label == label => (
+                tracking.phase_started.unwrap_or(now),
+                tracking.phase_start_done,
+            ),
+            _ => (now, done),
+        };
+        tracking.phase_started = Some(phase_started);
+        tracking.phase_start_done = phase_start_done;
+
+        let elapsed = now - phase_started;
+        let update = ProgressUpdate {
+            label,
+            done,
+            total,
+            elapsed,
+            eta: estimate_remaining(elapsed, done.saturating_sub(phase_start_done), done, total),
+        };
+
+        let report_due = match &self.inner.reporter {
+            Some((interval, _)) => tracking.last_report.is_none_or(|last| now - last >= *interval),
+            None => false,
+        };
+
+        if report_due {
+            tracking.last_report = Some(now);
+        }
+
+        tracking.latest = Some(update.clone());
+
+        let stop_reason = self.stop_reason(tracking.deadline);
+        if stop_reason.is_some() {
+            tracking.stop_reason = stop_reason;
+        }
+
+        // The reporter is called without holding the lock so it's free to use the handle itself
+        drop(tracking);
+
+        if let (true, Some((_, reporter))) = (report_due, &self.inner.reporter) {
+            reporter(&update);
+        }
+
+        match stop_reason {
+            Some(reason) => Err(Interrupted {
+                reason,
+                progress: Some(update),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Calls the reporter with the latest progress at most once per interval.
+    pub fn with_reporter(
+        mut self,
+        interval: Duration,
+        reporter: impl Fn(&ProgressUpdate) + Send + Sync + 'static,
+    ) -> Self {
+        self.state_mut().reporter = Some((interval, Box::new(reporter)));
+        self
+    }
+
+    /// Stops solvers once the timeout has passed, measured from the start of each
+    /// [`Progress::scope`] so every part the runner solves gets the whole timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.state_mut().timeout = Some(timeout);
+        self
+    }
+
+    fn state_mut(&mut self) -> &mut ProgressState {
+        Arc::get_mut(&mut self.inner).expect("progress handles must be configured before sharing")
+    }
+
+    fn stop_reason(&self, deadline: Option<Instant>) -> Option<StopReason> {
+        if self.inner.cancelled.load(Ordering::SeqCst) {
+            return Some(StopReason::Cancelled);
+        }
+
+        match deadline {
+            Some(deadline) if Instant::now() >= deadline => Some(StopReason::TimedOut),
+            _ => None,
+        }
+    }
+}
+
+/// A snapshot of how far along a solver is.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgressUpdate {
+    pub label: &'static str,
+    pub done: u64,
+    pub total: Option<u64>,
+
+    pub elapsed: Duration,
+    /// Estimated time until completion, only known when the total amount of work is.
+    pub eta: Option<Duration>,
+}
+
+impl std::fmt::Display for ProgressUpdate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.total {
+            Some(total) => {
+                let percent = self.done as f64 / total.max(1) as f64 * 100.0;
+                write!(f, "{} of {} {} ({percent:.2}%)", self.done, total, self.label)?;
+            }
+            None => write!(f, "{} {}", self.done, self.label)?,
+        }
+
+        write!(f, " in {}", format_duration(self.elapsed))?;
+
+        if let Some(eta) = self.eta {
+            write!(f, ", about {} remaining", format_duration(eta))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StopReason {
+    Cancelled,
+    TimedOut,
+}
+
+/// Returned by [`Progress::update`] when a solver should stop early, along with the last progress
+/// the solver reported which serves as its partial result.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Interrupted {
+    pub reason: StopReason,
+    pub progress: Option<ProgressUpdate>,
+}
+
+impl std::fmt::Display for Interrupted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.reason {
+            StopReason::Cancelled => write!(f, "cancelled")?,
+            StopReason::TimedOut => write!(f, "timed out")?,
+        }
+
+        if let Some(progress) = &self.progress {
+            write!(f, " after {progress}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for Interrupted {}
+
+/// Extrapolates the time left from the rate of the work done during `elapsed`, which can be less
+/// than the overall `done` when the solver resumed part way through.
+fn estimate_remaining(
+    elapsed: Duration,
+    done_in_elapsed: u64,
+    done: u64,
+    total: Option<u64>,
+) -> Option<Duration> {
+    let total = total?;
+
+    if done_in_elapsed == 0 || done > total {
+        return None;
+    }
+
+    Some(elapsed.mul_f64((total - done) as f64 / done_in_elapsed as f64))
+}
+
+/// Formats a duration to the nearest second, such as `5h 2m 13s`.
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (hours, minutes, seconds) = (secs / 3600, (secs / 60) % 60, secs % 60);
+
+    match (hours, minutes) {
+        (0, 0) => format!("{seconds}s"),
+        (0, _) => format!("{minutes}m {seconds}s"),
+        _ => format!("{hours}h {minutes}m {seconds}s"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancellation() {
+        let progress = Progress::new();
+
+        assert!(progress.update("rows scanned", 1, Some(10)).is_ok());
+        assert_eq!(progress.interruption(), None);
+
+        progress.clone().cancel();
+        let err = progress.update("rows scanned", 2, Some(10)).unwrap_err();
+
+        assert_eq!(err.reason, StopReason::Cancelled);
+        assert_eq!(err.progress.as_ref().map(|p| p.done), Some(2));
+        assert_eq!(progress.interruption(), Some(err));
+    }
+
+    #[test]
+    fn test_timeout() {
+        let progress = Progress::new().with_timeout(Duration::ZERO);
+
+        // The timeout only runs while a phase is in scope
+        assert!(!progress.should_stop());
+        assert!(progress.scope(|| progress.should_stop()));
+
+        let err = progress.scope(|| progress.update("grains dropped", 12, None)).unwrap_err();
+        assert_eq!(err.reason, StopReason::TimedOut);
+        assert_eq!(err.to_string(), "timed out after 12 grains dropped in 0s");
+    }
+
+    #[test]
+    fn test_timeout_per_phase() {
+        let progress = Progress::new().with_timeout(Duration::from_secs(1));
+
+        // The first phase uses most of the timeout, the second still gets all of it
+        progress.scope(|| {
+            std::thread::sleep(Duration::from_millis(700));
+            assert!(progress.update("rows scanned", 1, None).is_ok());
+        });
+
+        progress.scope(|| {
+            std::thread::sleep(Duration::from_millis(700));
+            assert!(progress.update("grains dropped", 1, None).is_ok());
+
+            std::thread::sleep(Duration::from_millis(400));
+            let err = progress.update("grains dropped", 2, None).unwrap_err();
+            assert_eq!(err.reason, StopReason::TimedOut);
+        });
+
+        // A phase that timed out doesn't stop the next one
+        progress.scope(|| assert!(!progress.should_stop()));
+        assert_eq!(progress.interruption(), None);
+    }
+
+    #[test]
+    fn test_reporting_interval() {
+        let reported = Arc::new(Mutex::new(vec![]));
+        let reported_clone = reported.clone();
+
+        let progress = Progress::new().with_reporter(Duration::from_secs(3600), move |update| {
+            reported_clone.lock().unwrap().push(update.done);
+        });
+
+        for done in 0..100 {
+            progress.update("rows scanned", done, Some(100)).unwrap();
+        }
+
+        // Only the first update falls outside of the reporting interval
+        assert_eq!(*reported.lock().unwrap(), vec![0]);
+    }
+
+    #[test]
+    fn test_scoped_current_handle() {
+        let progress = Progress::new();
+        progress.cancel();
+
+        assert!(!current().should_stop());
+        assert!(progress.scope(|| current().should_stop()));
+        assert!(!current().should_stop());
+    }
+
+    #[test]
+    fn test_eta_and_display() {
+        let eta = estimate_remaining(Duration::from_secs(10), 25, 25, Some(100));
+        assert_eq!(eta, Some(Duration::from_secs(30)));
+
+        // Resumed work only counts what was done since resuming towards the rate
+        let eta = estimate_remaining(Duration::from_secs(10), 25, 50, Some(100));
+        assert_eq!(eta, Some(Duration::from_secs(20)));
+        assert_eq!(estimate_remaining(Duration::from_secs(10), 0, 0, Some(100)), None);
+        assert_eq!(estimate_remaining(Duration::from_secs(10), 25, 25, None), None);
+
+        let update = ProgressUpdate {
+            label: "rows scanned",
+            done: 1_000_000,
+            total: Some(4_000_001),
+            elapsed: Duration::from_secs(3 * 3600 + 125),
+            eta: Some(Duration::from_secs(9 * 3600)),
+        };
+
+        assert_eq!(
+            update.to_string(),
+            "1000000 of 4000001 rows scanned (25.00%) in 3h 2m 5s, about 9h 0m 0s remaining"
+        );
+    }
+}
@@ -0,0 +1,304 @@
+use std::cell::RefCell;
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+
+thread_local! {
+    static CURRENT: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+}
+
+/// Returns the recorder installed for the solution running on this thread by
+/// [`Recorder::scope`], if recording was requested.
+pub fn current() -> Option<Recorder> {
+    CURRENT.with(|c| c.borrow().clone())
+}
+
+pub type Rgb = [u8; 3];
+
+/// A rendered image of a simulation at one step. The origin places the frame within the
+/// simulation, so frames of an area that grows over time still line up when they're exported.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Frame {
+    pub left: isize,
+    pub top: isize,
+    pub width: usize,
+    pub height: usize,
+
+    pixels: Vec<Rgb>,
+}
+
+impl Frame {
+    /// Renders a frame by calling `color` with the column and row of every pixel, relative to the
+    /// top left corner of the frame.
+    pub fn from_fn(width: usize, height: usize, mut color: impl FnMut(usize, usize) -> Rgb) -> Self {
+        let mut pixels = Vec::with_capacity(width * height);
+
+        for row in 0..height {
+            for column in 0..width {
+                pixels.push(color(column, row));
+            }
+        }
+
+        Frame {
+            left: 0,
+            top: 0,
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn pixel(&self, column: usize, row: usize) -> Rgb {
+        self.pixels[column + row * self.width]
+    }
+
+    pub fn with_origin(mut self, left: isize, top: isize) -> Self {
+        self.left = left;
+        self.top = top;
+        self
+    }
+}
+
+/// Receives the frames of a recording as they're captured, such as an encoder writing them to a
+/// file. Only the frame being written is held in memory, however long the simulation runs.
+pub trait FrameSink: Send {
+    fn write_frame(&mut self, frame: Frame) -> Result<(), String>;
+
+    /// Called once the recording is over, after the last frame was written.
+    fn finish(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl FrameSink for Sender<Frame> {
+    fn write_frame(&mut self, frame: Frame) -> Result<(), String> {
+        self.send(frame).map_err(|_| "nothing is receiving the recorded frames".to_string())
+    }
+}
+
+/// Captures frames from step based simulations, one every so many steps, and passes each one on
+/// to a [`FrameSink`] as soon as it's captured. Simulations report each step with
+/// [`Recorder::step`] and their final state with [`Recorder::finish`], the frames are only
+/// rendered when they're captured.
+#[derive(Clone)]
+pub struct Recorder {
+    inner: Arc<Mutex<RecorderState>>,
+}
+
+struct RecorderState {
+    every: usize,
+    /// Most frames captured, the final state included.
+    limit: usize,
+    steps: usize,
+    captured: usize,
+    /// Whether the most recent step was captured, so finishing doesn't capture it twice.
+    captured_last: bool,
+    /// Whether steps that were due went uncaptured because of the limit.
+    truncated: bool,
+
+    sink: Box<dyn FrameSink>,
+    /// The first error writing a frame, nothing more is captured after it.
+    error: Option<String>,
+}
+
+impl RecorderState {
+    fn capture(&mut self, frame: impl FnOnce() -> Frame) {
+        if self.error.is_some() {
+            return;
+        }
+
+        match self.sink.write_frame(frame()) {
+            Ok(()) => self.captured += 1,
+            Err(err) => self.error = Some(err),
+        }
+    }
+}
+
+impl Recorder {
+    /// Ends the recording, returning the number of frames written or the first error writing
+    /// them.
+    pub fn close(&self) -> Result<usize, String> {
+        let mut state = self.inner.lock().unwrap();
+
+        if let Some(err) = state.error.take() {
+            return Err(err);
+        }
+
+        state.sink.finish()?;
+        Ok(state.captured)
+    }
+
+    /// Captures the final state of the simulation, unless it was the last step captured.
+    pub fn finish(&self, frame: impl FnOnce() -> Frame) {
+        let mut state = self.inner.lock().unwrap();
+
+        if !state.captured_last && state.captured < state.limit {
+            state.capture(frame);
+            state.captured_last = true;
+        }
+    }
+
+    /// Creates a recorder capturing a frame every `every` steps, starting with the first, and
+    /// writing them to the sink.
+    pub fn new(every: usize, sink: impl FrameSink + 'static) -> Self {
+        Recorder {
+            inner: Arc::new(Mutex::new(RecorderState {
+                every: every.max(1),
+                limit: usize::MAX,
+                steps: 0,
+                captured: 0,
+                captured_last: false,
+                truncated: false,
+
+                sink: Box::new(sink),
+                error: None,
+            })),
+        }
+    }
+
+    /// Installs this recorder as the [`current`] one for the duration of the closure.
+    pub fn scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        struct Restore(Option<Recorder>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let previous = self.0.take();
+                CURRENT.with(|c| *c.borrow_mut() = previous);
+            }
+        }
+
+        let previous = CURRENT.with(|c| c.borrow_mut().replace(self.clone()));
+        let _restore = Restore(previous);
+
+        f()
+    }
+
+    /// Counts a step of the simulation, capturing the frame produced by the closure when it's
+    /// time for the next one. Once only one frame is left under the limit, steps are no longer
+    /// captured so the final state still is.
+    pub fn step(&self, frame: impl FnOnce() -> Frame) {
+        let mut state = self.inner.lock().unwrap();
+
+        let due = state.steps.is_multiple_of(state.every);
+        state.captured_last = due && state.captured + 1 < state.limit;
+        state.truncated |= due && !state.captured_last;
+
+        if state.captured_last {
+            state.capture(frame);
+        }
+
+        state.steps += 1;
+    }
+
+    /// Whether the limit stopped steps from being captured.
+    pub fn truncated(&self) -> bool {
+        self.inner.lock().unwrap().truncated
+    }
+
+    /// Limits the recording to `limit` frames, the final state of the simulation included.
+    pub fn with_limit(self, limit: usize) -> Self {
+        self.inner.lock().unwrap().limit = limit.max(1);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::mpsc::{channel, Receiver};
+
+    fn numbered(number: u8) -> Frame {
+        Frame::from_fn(1, 1, |_, _| [number, 0, 0])
+    }
+
+    fn captured(frames: &Receiver<Frame>) -> Vec<u8> {
+        frames.try_iter().map(|f| f.pixel(0, 0)[0]).collect()
+    }
+
+    #[test]
+    fn test_capture_interval() {
+        let (sender, frames) = channel();
+        let recorder = Recorder::new(3, sender);
+
+        for step in 0..7 {
+            recorder.step(|| numbered(step));
+        }
+        recorder.finish(|| numbered(7));
+
+        assert_eq!(captured(&frames), vec![0, 3, 6]);
+        assert_eq!(recorder.close(), Ok(3));
+
+        let (sender, frames) = channel();
+        let recorder = Recorder::new(3, sender);
+
+        for step in 0..5 {
+            recorder.step(|| numbered(step));
+        }
+        recorder.finish(|| numbered(5));
+
+        assert_eq!(captured(&frames), vec![0, 3, 5]);
+        assert_eq!(recorder.close(), Ok(3));
+    }
+
+    #[test]
+    fn test_frame_limit() {
+        let (sender, frames) = channel();
+        let recorder = Recorder::new(2, sender).with_limit(3);
+        let mut rendered = 0;
+
+        for step in 0..100 {
+            recorder.step(|| {
+                rendered += 1;
+                numbered(step)
+            });
+        }
+        recorder.finish(|| numbered(100));
+
+        // The last frame under the limit is kept for the final state
+        assert_eq!(captured(&frames), vec![0, 2, 100]);
+        assert_eq!(rendered, 2);
+        assert!(recorder.truncated());
+        assert_eq!(recorder.close(), Ok(3));
+    }
+
+    #[test]
+    fn test_sink_errors() {
+        let (sender, frames) = channel();
+        let recorder = Recorder::new(1, sender);
+
+        recorder.step(|| numbered(0));
+        drop(frames);
+
+        let mut rendered = 0;
+        for step in 1..10 {
+            recorder.step(|| {
+                rendered += 1;
+                numbered(step)
+            });
+        }
+
+        // Nothing more is rendered once a frame couldn't be written
+        assert_eq!(rendered, 1);
+        assert_eq!(recorder.close(), Err("nothing is receiving the recorded frames".to_string()));
+    }
+
+    #[test]
+    fn test_scoped_recorder() {
+        assert!(current().is_none());
+
+        let (sender, frames) = channel();
+        let recorder = Recorder::new(1, sender);
+        recorder.scope(|| current().unwrap().step(|| numbered(1)));
+
+        assert!(current().is_none());
+        assert_eq!(captured(&frames), vec![1]);
+    }
+
+    #[test]
+    fn test_frame_pixels() {
+        let frame = Frame::from_fn(3, 2, |column, row| [column as u8, row as u8, 0]).with_origin(-1, 4);
+
+        assert_eq!(frame.pixel(2, 1), [2, 1, 0]);
+        assert_eq!((frame.left, frame.top), (-1, 4));
+    }
+}
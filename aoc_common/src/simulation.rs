@@ -0,0 +1,26 @@
+use crate::Frame;
+
+/// What happened during a single step of a [`Simulation`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Step {
+    /// The simulation moved on and has more steps to run.
+    Running,
+    /// The step completed something worth stopping at, such as a grain of sand coming to rest or
+    /// an instruction finishing. What counts as a milestone depends on the simulation.
+    Milestone,
+    /// The simulation has finished and shouldn't be stepped any further.
+    Finished,
+}
+
+/// A step based simulation that can be advanced and inspected one step at a time, so it can be
+/// explored interactively instead of only seeing its final state.
+pub trait Simulation {
+    /// Runs a single step of the simulation.
+    fn step(&mut self) -> Step;
+
+    /// Renders the current state of the simulation.
+    fn frame(&self) -> Frame;
+
+    /// Internal state worth showing alongside the frame, as name and value pairs.
+    fn state(&self) -> Vec<(&'static str, String)>;
+}
@@ -0,0 +1,179 @@
+//! Snapshot testing for rendered grids such as day 10's CRT and day 14's cave. The expected
+//! rendering is kept in a file in the day's `snapshots` directory, next to its `data`, instead of
+//! being written out by hand in the test.
+//!
+//! Snapshots are recorded, or re-recorded after an intended change, by running the tests with
+//! the [`UPDATE_ENV`] environment variable set:
+//!
+//! ```text
+//! UPDATE_SNAPSHOTS=1 cargo test -p day_14
+//! ```
+
+use std::path::Path;
+
+/// Environment variable that makes [`assert_snapshot!`](crate::assert_snapshot) record the
+/// rendering instead of comparing against the saved snapshot.
+pub const UPDATE_ENV: &str = "UPDATE_SNAPSHOTS";
+
+/// Compares a rendered grid against the snapshot with the given name in the calling crate's
+/// `snapshots` directory, panicking with a visual diff of the cells that differ.
+///
+/// ```ignore
+/// aoc_common::assert_snapshot!("sample_display", cpu.display_string());
+/// ```
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($name:expr, $actual:expr $(,)?) => {{
+        let dir = ::std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("snapshots");
+        let update = ::std::env::var_os($crate::snapshot::UPDATE_ENV).is_some();
+
+        if let Err(err) = $crate::snapshot::check(&dir, $name, &$actual, update) {
+            panic!("{err}");
+        }
+    }};
+}
+
+/// Compares the rendering with the snapshot `name` in `dir`, or saves it as the new snapshot when
+/// `update` is set. Snapshots are stored with a trailing newline, which isn't part of the
+/// rendering.
+pub fn check(dir: &Path, name: &str, actual: &str, update: bool) -> Result<(), String> {
+    let path = dir.join(format!("{name}.txt"));
+
+    if update {
+        std::fs::create_dir_all(dir)
+            .and_then(|_| std::fs::write(&path, format!("{actual}\n")))
+            .map_err(|err| format!("unable to record snapshot {}: {err}", path.display()))?;
+
+        return Ok(());
+    }
+
+    let expected = match std::fs::read_to_string(&path) {
+        Ok(expected) => expected,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Err(format!(
+                "snapshot {} doesn't exist, run the tests with {UPDATE_ENV}=1 to record it",
+                path.display()
+            ));
+        }
+        Err(err) => return Err(format!("unable to read snapshot {}: {err}", path.display())),
+    };
+
+    let expected = expected.strip_suffix('\n').unwrap_or(&expected);
+
+    match diff(expected, actual) {
+        None => Ok(()),
+        Some(diff) => Err(format!(
+            "rendering doesn't match snapshot {}, run the tests with {UPDATE_ENV}=1 if the \
+             change is intended\n{diff}",
+            path.display()
+        )),
+    }
+}
+
+/// Lays the expected and actual grids out side by side with a third column marking every cell
+/// that differs with `^`, rows with differences are marked with `>`. Cells missing from one of
+/// the grids count as different. `None` when the grids are the same.
+pub fn diff(expected: &str, actual: &str) -> Option<String> {
+    if expected == actual {
+        return None;
+    }
+
+    let expected: Vec<Vec<char>> = expected.lines().map(|l| l.chars().collect()).collect();
+    let actual: Vec<Vec<char>> = actual.lines().map(|l| l.chars().collect()).collect();
+
+    let rows = expected.len().max(actual.len());
+    let width = expected.iter().chain(&actual).map(Vec::len).max().unwrap_or(0);
+    let column_width = width.max("expected".len());
+
+    let mut differing_cells = 0;
+    let mut output = vec![format!(
+        "    {:column_width$}  {:column_width$}  differences",
+        "expected", "actual"
+    )];
+
+    for row in 0..rows {
+        let cell = |grid: &[Vec<char>], column: usize| grid.get(row)?.get(column).copied();
+
+        let markers: String = (0..width)
+            .map(|column| {
+                if cell(&expected, column) == cell(&actual, column) {
+                    ' '
+                } else {
+                    differing_cells += 1;
+                    '^'
+                }
+            })
+            .collect();
+
+        let line = |grid: &[Vec<char>]| grid.get(row).map_or(String::new(), |l| l.iter().collect());
+        let marker = if markers.trim().is_empty() { ' ' } else { '>' };
+
+        output.push(
+            format!(
+                "{marker}{:>2} {:column_width$}  {:column_width$}  {markers}",
+                row + 1,
+                line(&expected),
+                line(&actual),
+            )
+            .trim_end()
+            .to_string(),
+        );
+    }
+
+    output.push(format!("{differing_cells} cells differ"));
+
+    Some(output.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        assert_eq!(diff("#.\n.#", "#.\n.#"), None);
+
+        assert_eq!(
+            diff("#..\n.#.\n..#", "#..\n...\n..#").unwrap(),
+            [
+                "    expected  actual    differences",
+                "  1 #..       #..",
+                "> 2 .#.       ...        ^",
+                "  3 ..#       ..#",
+                "1 cells differ",
+            ]
+            .join("\n")
+        );
+
+        // A missing row or column differs in every cell
+        assert_eq!(
+            diff("##\n##", "###").unwrap(),
+            [
+                "    expected  actual    differences",
+                "> 1 ##        ###         ^",
+                "> 2 ##                  ^^",
+                "3 cells differ",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_record_and_check() {
+        let dir = std::env::temp_dir().join(format!("snapshot-test-{}", std::process::id()));
+
+        let err = check(&dir, "grid", "#.\n.#", false).unwrap_err();
+        assert!(err.contains("doesn't exist, run the tests with UPDATE_SNAPSHOTS=1"));
+
+        check(&dir, "grid", "#.\n.#", true).unwrap();
+        assert_eq!(std::fs::read_to_string(dir.join("grid.txt")).unwrap(), "#.\n.#\n");
+
+        assert_eq!(check(&dir, "grid", "#.\n.#", false), Ok(()));
+
+        let err = check(&dir, "grid", "#.\n##", false).unwrap_err();
+        assert!(err.starts_with("rendering doesn't match snapshot"));
+        assert!(err.ends_with("1 cells differ"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
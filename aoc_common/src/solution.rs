@@ -0,0 +1,146 @@
+use std::process::ExitCode;
+
+use serde::de::DeserializeOwned;
+
+use crate::generate::InputRng;
+use crate::{normalize, InputSource, ParseError, Simulation};
+
+/// Which half of a day's puzzle is being solved.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl std::fmt::Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Part, String> {
+        match value {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            _ => Err(format!("there is no part {value}, only 1 or 2")),
+        }
+    }
+}
+
+/// The common shape of every day's puzzle. The input is parsed once and shared between both parts
+/// so the parsing cost can be measured independently from the solutions themselves.
+///
+/// Answers are returned as strings as they're only ever displayed or compared. A part that can't
+/// produce an answer (such as the last day which only has a single puzzle) returns `None`.
+pub trait Solution {
+    /// The day of the advent calendar this solves.
+    const DAY: u8;
+
+    /// Directory holding the day's sample and bundled puzzle input. This should always be
+    /// `concat!(env!("CARGO_MANIFEST_DIR"), "/data")` so it resolves to the day's own crate.
+    const DATA_DIR: &'static str;
+
+    /// Version of the day's crate, this should always be `env!("CARGO_PKG_VERSION")`. Answers
+    /// cached by the runner are only reused by the same version built from the same sources, so
+    /// editing the solution invalidates them without a version bump.
+    const VERSION: &'static str;
+
+    type Input;
+
+    /// Parameters of the puzzle that differ between inputs, such as the row day 15 looks at
+    /// which is different for the sample. The defaults are the values for the real input, days
+    /// read them with [`params::current`](crate::params::current) while parsing or solving. Days
+    /// without any use [`NoParams`](crate::NoParams).
+    type Params: DeserializeOwned + Default;
+
+    /// Parses the puzzle input. The runners [normalize](crate::normalize) the input first, so
+    /// parsers can rely on LF line endings and no trailing whitespace.
+    ///
+    /// The runners read the whole input into memory before parsing it, as they hash it to tell
+    /// which input answers and checkpoints belong to. Inputs too large to load are handled by
+    /// giving a file to the days' `BufRead` functions directly, such as
+    /// `day_01::top_elf_calories` or `day_02::process_first_data`.
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError>;
+
+    fn part_one(input: &Self::Input) -> Option<String>;
+
+    fn part_two(input: &Self::Input) -> Option<String>;
+
+    /// Generates a random but valid puzzle input with roughly `size` entries, what an entry is (an
+    /// elf, a round, an instruction...) depends on the day. Days without a generator return
+    /// `None`.
+    fn generate(_rng: &mut InputRng, _size: usize) -> Option<String> {
+        None
+    }
+
+    /// The simulation used to solve a part, set up to be stepped through interactively. Days
+    /// that aren't solved by simulating anything return `None`.
+    fn simulate(_input: &Self::Input, _part: Part) -> Option<Box<dyn Simulation>> {
+        None
+    }
+
+    fn solve(input: &Self::Input, part: Part) -> Option<String> {
+        match part {
+            Part::One => Self::part_one(input),
+            Part::Two => Self::part_two(input),
+        }
+    }
+}
+
+/// Solves and prints both parts of a single day, this is all the individual day binaries need to
+/// do. The input argument is interpreted by [`InputSource::from_arg`].
+///
+/// Only warnings logged by the solution are shown, the `aoc` runner can show the rest.
+pub fn run<S: Solution>(input_arg: Option<&str>) -> ExitCode {
+    let _ = tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_max_level(tracing::Level::WARN)
+        .try_init();
+
+    let data = match InputSource::from_arg(input_arg, S::DATA_DIR).read() {
+        Ok(data) => data,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let input = match S::parse(&normalize(&data).data) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("{}", err.render());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    for part in Part::ALL {
+        let answer = S::solve(&input, part);
+        println!("Day {:02} part {part}: {}", S::DAY, display_answer(answer.as_deref()));
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// Formats an answer for the terminal. Some answers are rendered displays spanning multiple lines,
+/// those get started on their own line so the first row lines up with the rest.
+pub fn display_answer(answer: Option<&str>) -> String {
+    match answer {
+        Some(answer) if answer.contains('\n') => format!("\n{answer}"),
+        Some(answer) => answer.to_string(),
+        None => "no answer".to_string(),
+    }
+}
@@ -0,0 +1,40 @@
+use aoc_common::generate::Rng;
+
+/// A calorie list for `elves` elves, each carrying between one and fifteen snacks.
+pub fn calorie_list(rng: &mut impl Rng, elves: usize) -> String {
+    let mut list = String::new();
+
+    for elf in 0..elves {
+        // The elves are separated by a blank line
+        if elf > 0 {
+            list.push('\n');
+        }
+
+        for _ in 0..rng.gen_range(1..=15) {
+            list.push_str(&rng.gen_range(1000..=60000).to_string());
+            list.push('\n');
+        }
+    }
+
+    list
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::count_elf_calories;
+    use aoc_common::generate::seeded;
+
+    #[test]
+    fn test_generated_calorie_list() {
+        let input = calorie_list(&mut seeded(1), 200);
+        assert_eq!(input, calorie_list(&mut seeded(1), 200));
+
+        let elves = count_elf_calories(input.as_bytes()).unwrap();
+        assert_eq!(elves.len(), 200);
+        assert!(elves.iter().all(|(_, calories)| (1000..=900000).contains(calories)));
+
+        assert_eq!(calorie_list(&mut seeded(1), 0), "");
+    }
+}
@@ -0,0 +1,155 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::io::BufRead;
+
+use aoc_common::diagnostics::for_each_line;
+use aoc_common::generate::InputRng;
+use aoc_common::{NoParams, ParseError, Solution};
+
+pub mod generate;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const DATA_DIR: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/data");
+    const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+
+    type Input = Vec<(usize, usize)>;
+    type Params = NoParams;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        count_elf_calories(data)
+    }
+
+    fn part_one(input: &Self::Input) -> Option<String> {
+        // The elves are sorted in ascending order of calories carried
+        input.last().map(|(_, count)| count.to_string())
+    }
+
+    fn part_two(input: &Self::Input) -> Option<String> {
+        let top_three = input
+            .iter()
+            .rev()
+            .take(3)
+            .try_fold(0_usize, |total, (_, count)| total.checked_add(*count))?;
+
+        Some(top_three.to_string())
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generate::calorie_list(rng, size))
+    }
+}
+
+/// Totals the calories carried by each elf, returned as `(elf, calories)` pairs sorted in
+/// ascending order of calories.
+pub fn count_elf_calories<R: BufRead>(reader: R) -> Result<Vec<(usize, usize)>, ParseError> {
+    let mut all_elves = vec![];
+
+    for_each_elf(reader, |elf, count| all_elves.push((elf, count)))?;
+    all_elves.sort_by_key(|a| a.1);
+
+    tracing::debug!(elves = all_elves.len(), "counted the calories carried by each elf");
+
+    Ok(all_elves)
+}
+
+/// The largest calorie totals carried by any elf, largest first. Only the requested number of
+/// totals is kept while reading so this works on inputs of any size.
+pub fn top_elf_calories<R: BufRead>(reader: R, count: usize) -> Result<Vec<usize>, ParseError> {
+    let mut top = BinaryHeap::with_capacity(count + 1);
+
+    for_each_elf(reader, |_, calories| {
+        top.push(Reverse(calories));
+
+        if top.len() > count {
+            top.pop();
+        }
+    })?;
+
+    Ok(top.into_sorted_vec().into_iter().map(|Reverse(calories)| calories).collect())
+}
+
+/// Reads the calorie list line by line, calling `on_elf` with each elf's number and total as
+/// soon as their list ends.
+fn for_each_elf<R: BufRead>(reader: R, mut on_elf: impl FnMut(usize, usize)) -> Result<(), ParseError> {
+    // The elfs are one-indexed
+    let mut current_elf = 1;
+    let mut current_count = 0;
+
+    for_each_line(reader, |line| {
+        if line.trim().is_empty() {
+            on_elf(current_elf, current_count);
+
+            current_count = 0;
+            current_elf += 1;
+
+            return Ok(());
+        }
+
+        let calories = line
+            .trim()
+            .parse::<usize>()
+            .map_err(|_| ParseError::at(line, line.trim(), "expected a calorie count"))?;
+
+        current_count = current_count
+            .checked_add(calories)
+            .ok_or_else(|| ParseError::at(line, line.trim(), "elf is carrying too many calories"))?;
+
+        Ok(())
+    })?;
+
+    on_elf(current_elf, current_count);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &[u8] = include_bytes!("../data/sample");
+
+    #[test]
+    fn test_sample_input() {
+        let elf_counts = count_elf_calories(SAMPLE_INPUT).unwrap();
+
+        assert_eq!(
+            elf_counts,
+            vec![(2, 4000), (1, 6000), (5, 10000), (3, 11000), (4, 24000)]
+        );
+    }
+
+    #[test]
+    fn test_sample_answers() {
+        let elf_counts = Day01::parse(SAMPLE_INPUT).unwrap();
+
+        assert_eq!(Day01::part_one(&elf_counts), Some("24000".to_string()));
+        assert_eq!(Day01::part_two(&elf_counts), Some("45000".to_string()));
+    }
+
+    #[test]
+    fn test_invalid_calorie_count() {
+        let err = count_elf_calories(&b"1000\n2000\n\n30o0\n"[..]).unwrap_err();
+
+        assert_eq!(err.line_number(), 4);
+        assert_eq!(err.columns(), 1..5);
+
+        let err = count_elf_calories(&b"1000\n18446744073709551615\n"[..]).unwrap_err();
+        assert_eq!(err.line_number(), 2);
+        assert_eq!(err.message(), "elf is carrying too many calories");
+
+        // Each elf's total fits but the top three together don't
+        let elf_counts = Day01::parse(&b"18446744073709551615\n\n5\n"[..]).unwrap();
+        assert_eq!(Day01::part_one(&elf_counts), Some("18446744073709551615".to_string()));
+        assert_eq!(Day01::part_two(&elf_counts), None);
+    }
+
+    #[test]
+    fn test_top_elf_calories() {
+        assert_eq!(top_elf_calories(SAMPLE_INPUT, 3).unwrap(), vec![24000, 11000, 10000]);
+        assert_eq!(top_elf_calories(SAMPLE_INPUT, 1).unwrap(), vec![24000]);
+        assert_eq!(top_elf_calories(SAMPLE_INPUT, 10).unwrap().len(), 5);
+    }
+}
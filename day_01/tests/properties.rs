@@ -0,0 +1,40 @@
+use aoc_common::generate::seeded;
+use aoc_common::Solution;
+use day_01::{count_elf_calories, generate, top_elf_calories, Day01};
+use proptest::prelude::*;
+
+/// Solves both parts whenever the data parses, neither may panic.
+fn solve(data: &[u8]) {
+    if let Ok(input) = Day01::parse(data) {
+        let _ = Day01::part_one(&input);
+        let _ = Day01::part_two(&input);
+    }
+}
+
+proptest! {
+    #[test]
+    fn test_arbitrary_bytes_never_panic(data in prop::collection::vec(any::<u8>(), 0..256)) {
+        solve(&data);
+    }
+
+    #[test]
+    fn test_arbitrary_calories_never_panic(calories in prop::collection::vec((any::<u64>(), any::<bool>()), 0..8)) {
+        // Each calorie count may end an elf's list, so elves carry anything up to the limit
+        let lines: Vec<String> = calories
+            .iter()
+            .map(|&(calories, last)| if last { format!("{calories}\n") } else { calories.to_string() })
+            .collect();
+
+        solve(lines.join("\n").as_bytes());
+    }
+
+    #[test]
+    fn test_top_calories_match_sorted_totals(seed in any::<u64>(), elves in 0..50usize, count in 0..5usize) {
+        let input = generate::calorie_list(&mut seeded(seed), elves);
+
+        let all_elves = count_elf_calories(input.as_bytes()).unwrap();
+        let expected: Vec<usize> = all_elves.iter().rev().take(count).map(|(_, calories)| *calories).collect();
+
+        prop_assert_eq!(top_elf_calories(input.as_bytes(), count).unwrap(), expected);
+    }
+}
@@ -0,0 +1,12 @@
+use day_01::{count_elf_calories, top_elf_calories};
+
+const SAMPLE_INPUT: &[u8] = include_bytes!("../data/sample");
+
+#[test]
+fn test_sample_calories() {
+    let elves = count_elf_calories(SAMPLE_INPUT).unwrap();
+    assert_eq!(elves.len(), 5);
+    assert_eq!(elves.last(), Some(&(4, 24000)));
+
+    assert_eq!(top_elf_calories(SAMPLE_INPUT, 3).unwrap(), vec![24000, 11000, 10000]);
+}
@@ -0,0 +1,39 @@
+use aoc_common::generate::Rng;
+
+/// A strategy guide with `rounds` rounds, picked uniformly from every combination of columns.
+pub fn strategy_guide(rng: &mut impl Rng, rounds: usize) -> String {
+    let mut guide = String::with_capacity(rounds * 4);
+
+    for _ in 0..rounds {
+        guide.push(rng.gen_range('A'..='C'));
+        guide.push(' ');
+        guide.push(rng.gen_range('X'..='Z'));
+        guide.push('\n');
+    }
+
+    guide
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{process_first_data, process_second_data, Day02};
+    use aoc_common::generate::seeded;
+    use aoc_common::Solution;
+
+    #[test]
+    fn test_generated_strategy_guide() {
+        let input = strategy_guide(&mut seeded(2), 500);
+        assert_eq!(input, strategy_guide(&mut seeded(2), 500));
+
+        let guide = Day02::parse(input.as_bytes()).unwrap();
+        assert_eq!(guide.throws().len(), 500);
+
+        // Every round scores between 1 and 9 points
+        let (first, _) = process_first_data(input.as_bytes()).unwrap();
+        let (second, _) = process_second_data(input.as_bytes()).unwrap();
+        assert!((500..=4500).contains(&first));
+        assert!((500..=4500).contains(&second));
+    }
+}
@@ -0,0 +1,363 @@
+use std::io::BufRead;
+
+use aoc_common::diagnostics::{for_each_line, parse_lines};
+use aoc_common::generate::InputRng;
+use aoc_common::{NoParams, ParseError, Solution};
+
+pub mod generate;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const DATA_DIR: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/data");
+    const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+
+    type Input = StrategyGuide;
+    type Params = NoParams;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        Ok(StrategyGuide {
+            throws: parse_lines(data, parse_both_as_throws)?,
+            throw_results: parse_lines(data, parse_throw_results)?,
+        })
+    }
+
+    fn part_one(input: &Self::Input) -> Option<String> {
+        let results = score_throws(&input.throws);
+        let our_total_score: usize = results.iter().map(|(ours, _)| ours).sum();
+        Some(our_total_score.to_string())
+    }
+
+    fn part_two(input: &Self::Input) -> Option<String> {
+        let results = score_throw_results(&input.throw_results);
+        let our_total_score: usize = results.iter().map(|(ours, _)| ours).sum();
+        Some(our_total_score.to_string())
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generate::strategy_guide(rng, size))
+    }
+}
+
+/// The two parts read the second column of the strategy guide differently, so the guide is kept
+/// under both interpretations.
+#[derive(Debug)]
+pub struct StrategyGuide {
+    throws: Vec<(Throw, Throw)>,
+    throw_results: Vec<(Throw, RoundResult)>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RoundResult {
+    Win,
+    Tie,
+    Lose,
+}
+
+impl StrategyGuide {
+    /// The guide read as our throw for every round, as part one assumes.
+    pub fn throws(&self) -> &[(Throw, Throw)] {
+        &self.throws
+    }
+
+    /// The guide read as the result we need for every round, as part two explains.
+    pub fn throw_results(&self) -> &[(Throw, RoundResult)] {
+        &self.throw_results
+    }
+}
+
+impl RoundResult {
+    pub fn inverse(&self) -> RoundResult {
+        use RoundResult::*;
+
+        match self {
+            Win => Lose,
+            Tie => Tie,
+            Lose => Win,
+        }
+    }
+
+    pub fn point_value(&self) -> usize {
+        use RoundResult::*;
+
+        match self {
+            Win => 6,
+            Tie => 3,
+            Lose => 0,
+        }
+    }
+}
+
+impl TryFrom<&str> for RoundResult {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<RoundResult, String> {
+        use RoundResult::*;
+
+        match value {
+            "X" => Ok(Lose),
+            "Y" => Ok(Tie),
+            "Z" => Ok(Win),
+            _ => Err(format!("invalid round result {:?}, expected X, Y or Z", value)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Throw {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Throw {
+    pub fn looses_to(&self) -> Throw {
+        use Throw::*;
+
+        match self {
+            Rock => Paper,
+            Paper => Scissors,
+            Scissors => Rock,
+        }
+    }
+
+    pub fn point_value(&self) -> usize {
+        use Throw::*;
+
+        match self {
+            Rock => 1,
+            Paper => 2,
+            Scissors => 3,
+        }
+    }
+
+    pub fn wins_against(&self) -> Throw {
+        use Throw::*;
+
+        match self {
+            Rock => Scissors,
+            Paper => Rock,
+            Scissors => Paper,
+        }
+    }
+}
+
+impl TryFrom<&str> for Throw {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Throw, String> {
+        match value {
+            "A" | "X" => Ok(Throw::Rock),
+            "B" | "Y" => Ok(Throw::Paper),
+            "C" | "Z" => Ok(Throw::Scissors),
+            _ => Err(format!("invalid throw {:?}, expected A, B, C, X, Y or Z", value)),
+        }
+    }
+}
+
+pub fn choose_target_hand(throw: Throw, target_result: RoundResult) -> Throw {
+    use RoundResult::*;
+
+    match target_result {
+        Tie => throw,
+        Win => throw.looses_to(),
+        Lose => throw.wins_against(),
+    }
+}
+
+pub fn parse_both_as_throws(line: &str) -> Result<(Throw, Throw), ParseError> {
+    let (first, second) = split_columns(line)?;
+
+    Ok((
+        Throw::try_from(first).map_err(|msg| ParseError::at(line, first, msg))?,
+        Throw::try_from(second).map_err(|msg| ParseError::at(line, second, msg))?,
+    ))
+}
+
+pub fn parse_throw_results(line: &str) -> Result<(Throw, RoundResult), ParseError> {
+    let (first, second) = split_columns(line)?;
+
+    Ok((
+        Throw::try_from(first).map_err(|msg| ParseError::at(line, first, msg))?,
+        RoundResult::try_from(second).map_err(|msg| ParseError::at(line, second, msg))?,
+    ))
+}
+
+/// The columns can be separated by any amount of whitespace, guides that were hand edited
+/// sometimes end up with doubled spaces or tabs.
+fn split_columns(line: &str) -> Result<(&str, &str), ParseError> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+
+    match parts[..] {
+        [first, second] => Ok((first, second)),
+        _ => Err(ParseError::whole_line(
+            line,
+            "expected two columns separated by whitespace",
+        )),
+    }
+}
+
+/// Scores every round of the guide read as throws, see [`score_round`].
+pub fn score_throws(throws: &[(Throw, Throw)]) -> Vec<(usize, usize)> {
+    throws
+        .iter()
+        .map(|(other, me)| score_round(*other, *me))
+        .collect()
+}
+
+/// Scores every round of the guide read as results, picking the throw that gets each result.
+pub fn score_throw_results(throw_results: &[(Throw, RoundResult)]) -> Vec<(usize, usize)> {
+    throw_results
+        .iter()
+        .map(|(other, result)| (*other, choose_target_hand(*other, *result)))
+        .map(|(other, me)| score_round(other, me))
+        .collect()
+}
+
+/// The total scores `(ours, opponent's)` of the guide read as throws. The guide is scored as it's
+/// read so inputs of any size can be scored without keeping them in memory.
+pub fn process_first_data<R: BufRead>(reader: R) -> Result<(usize, usize), ParseError> {
+    total_scores(reader, |line| {
+        let (other, me) = parse_both_as_throws(line)?;
+        Ok(score_round(other, me))
+    })
+}
+
+/// The total scores `(ours, opponent's)` of the guide read as results, picking the throw that
+/// gets each result. Scored as it's read like [`process_first_data`].
+pub fn process_second_data<R: BufRead>(reader: R) -> Result<(usize, usize), ParseError> {
+    total_scores(reader, |line| {
+        let (other, result) = parse_throw_results(line)?;
+        Ok(score_round(other, choose_target_hand(other, result)))
+    })
+}
+
+fn total_scores<R: BufRead>(
+    reader: R,
+    mut score_line: impl FnMut(&str) -> Result<(usize, usize), ParseError>,
+) -> Result<(usize, usize), ParseError> {
+    let mut totals = (0, 0);
+
+    for_each_line(reader, |line| {
+        let (ours, theirs) = score_line(line)?;
+        totals = (totals.0 + ours, totals.1 + theirs);
+
+        Ok(())
+    })?;
+
+    Ok(totals)
+}
+
+/// Scores a single round as `(our score, opponent's score)`.
+pub fn score_round(opponent: Throw, our_strategy: Throw) -> (usize, usize) {
+    use RoundResult::*;
+
+    let our_result = match (our_strategy, opponent) {
+        (a, b) if a == b => Tie,
+        (a, b) if a.wins_against() == b => Win,
+        _ => Lose,
+    };
+
+    let scores = (
+        our_strategy.point_value() + our_result.point_value(),
+        opponent.point_value() + our_result.inverse().point_value(),
+    );
+
+    tracing::trace!(?opponent, ?our_strategy, ?our_result, ?scores, "scored round");
+
+    scores
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_DATA: &[u8] = include_bytes!("../data/sample");
+
+    #[test]
+    fn test_throw_throw_line_parser() {
+        use Throw::*;
+
+        let data = std::str::from_utf8(SAMPLE_DATA).unwrap();
+        let throws: Vec<(Throw, Throw)> = data
+            .lines()
+            .map(|l| parse_both_as_throws(l).unwrap())
+            .collect();
+
+        assert_eq!(
+            throws,
+            vec![(Rock, Paper), (Paper, Rock), (Scissors, Scissors)]
+        );
+    }
+
+    #[test]
+    fn test_throw_result_line_parser() {
+        use RoundResult::*;
+        use Throw::*;
+
+        let data = std::str::from_utf8(SAMPLE_DATA).unwrap();
+        let throws: Vec<(Throw, RoundResult)> =
+            data.lines().map(|l| parse_throw_results(l).unwrap()).collect();
+
+        assert_eq!(throws, vec![(Rock, Tie), (Paper, Lose), (Scissors, Win)]);
+    }
+
+    #[test]
+    fn test_target_hand_selection() {
+        use RoundResult::*;
+        use Throw::*;
+
+        assert_eq!(choose_target_hand(Rock, Tie), Rock);
+        assert_eq!(choose_target_hand(Paper, Lose), Rock);
+        assert_eq!(choose_target_hand(Scissors, Win), Rock);
+    }
+
+    #[test]
+    fn test_sample_input_first() {
+        let guide = Day02::parse(SAMPLE_DATA).unwrap();
+        let results = score_throws(&guide.throws);
+        let our_total_score: usize = results.iter().map(|(ours, _)| ours).sum();
+        assert_eq!(our_total_score, 15);
+    }
+
+    #[test]
+    fn test_sample_input_second() {
+        let guide = Day02::parse(SAMPLE_DATA).unwrap();
+        let results = score_throw_results(&guide.throw_results);
+        let our_total_score: usize = results.iter().map(|(ours, _)| ours).sum();
+        assert_eq!(our_total_score, 12);
+    }
+
+    #[test]
+    fn test_invalid_guide_lines() {
+        let err = Day02::parse(b"A Y\nB Q\n").unwrap_err();
+        assert_eq!(err.line_number(), 2);
+        assert_eq!(err.columns(), 3..4);
+
+        let err = Day02::parse(b"A Y\nBX\n").unwrap_err();
+        assert_eq!(err.line_number(), 2);
+        assert_eq!(err.columns(), 1..3);
+
+        let err = Day02::parse(b"A Y\nB X Z\n").unwrap_err();
+        assert_eq!(err.line_number(), 2);
+        assert_eq!(err.message(), "expected two columns separated by whitespace");
+    }
+
+    #[test]
+    fn test_irregular_column_spacing() {
+        let guide = Day02::parse(b"A  Y\nB\tX\n C Z").unwrap();
+
+        assert_eq!(Day02::part_one(&guide), Some("15".to_string()));
+        assert_eq!(Day02::part_two(&guide), Some("12".to_string()));
+    }
+
+    #[test]
+    fn test_streamed_total_scores() {
+        assert_eq!(process_first_data(SAMPLE_DATA), Ok((15, 15)));
+        assert_eq!(process_second_data(SAMPLE_DATA), Ok((12, 15)));
+
+        let err = process_first_data(&b"A Y\nB Q\n"[..]).unwrap_err();
+        assert_eq!(err.line_number(), 2);
+    }
+}
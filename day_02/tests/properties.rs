@@ -0,0 +1,52 @@
+use aoc_common::generate::seeded;
+use aoc_common::Solution;
+use day_02::{
+    generate, parse_both_as_throws, process_first_data, process_second_data, score_round, Day02,
+};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn test_arbitrary_bytes_never_panic(data in prop::collection::vec(any::<u8>(), 0..256)) {
+        if let Ok(guide) = Day02::parse(&data) {
+            let _ = Day02::part_one(&guide);
+            let _ = Day02::part_two(&guide);
+        }
+
+        let _ = process_first_data(&data[..]);
+        let _ = process_second_data(&data[..]);
+    }
+
+    #[test]
+    fn test_round_score_bounds(line in "[ABC] [XYZ]") {
+        let (opponent, ours) = parse_both_as_throws(&line).unwrap();
+        let (our_score, their_score) = score_round(opponent, ours);
+
+        // A win and a loss are worth 6 points between them, as are two ties
+        prop_assert!((1..=9).contains(&our_score));
+        prop_assert!((1..=9).contains(&their_score));
+        prop_assert_eq!(our_score + their_score, opponent.point_value() + ours.point_value() + 6);
+    }
+
+    #[test]
+    fn test_winning_throws_win(line in "[ABC] [XYZ]") {
+        let (opponent, _) = parse_both_as_throws(&line).unwrap();
+        let winning_throw = opponent.looses_to();
+
+        prop_assert_eq!(score_round(opponent, winning_throw).0, winning_throw.point_value() + 6);
+        prop_assert_eq!(score_round(opponent, opponent.wins_against()).1, opponent.point_value() + 6);
+    }
+
+    #[test]
+    fn test_streamed_scores_match_answers(seed in any::<u64>(), rounds in 0..100usize) {
+        let input = generate::strategy_guide(&mut seeded(seed), rounds);
+        let guide = Day02::parse(input.as_bytes()).unwrap();
+
+        let (first, _) = process_first_data(input.as_bytes()).unwrap();
+        let (second, _) = process_second_data(input.as_bytes()).unwrap();
+
+        prop_assert_eq!(Some(first.to_string()), Day02::part_one(&guide));
+        prop_assert_eq!(Some(second.to_string()), Day02::part_two(&guide));
+        prop_assert!((rounds..=9 * rounds).contains(&first));
+    }
+}
@@ -0,0 +1,33 @@
+use day_02::{
+    choose_target_hand, parse_both_as_throws, process_first_data, process_second_data,
+    score_round, score_throw_results, score_throws, RoundResult, Throw,
+};
+
+use aoc_common::Solution;
+
+const SAMPLE_INPUT: &[u8] = include_bytes!("../data/sample");
+
+#[test]
+fn test_sample_strategy_guide() {
+    let guide = day_02::Day02::parse(SAMPLE_INPUT).unwrap();
+    assert_eq!(guide.throws()[0], (Throw::Rock, Throw::Paper));
+    assert_eq!(guide.throw_results()[0], (Throw::Rock, RoundResult::Tie));
+
+    let first: usize = score_throws(guide.throws()).iter().map(|(ours, _)| ours).sum();
+    let second: usize = score_throw_results(guide.throw_results()).iter().map(|(ours, _)| ours).sum();
+
+    assert_eq!((first, second), (15, 12));
+    assert_eq!(process_first_data(SAMPLE_INPUT).unwrap().0, 15);
+    assert_eq!(process_second_data(SAMPLE_INPUT).unwrap().0, 12);
+}
+
+#[test]
+fn test_single_rounds() {
+    let (opponent, ours) = parse_both_as_throws("C Z").unwrap();
+    assert_eq!(score_round(opponent, ours), (6, 6));
+    assert_eq!(score_round(Throw::Rock, Throw::Paper), (8, 1));
+    assert_eq!(score_round(Throw::Paper, Throw::Rock), (1, 8));
+
+    assert_eq!(choose_target_hand(Throw::Scissors, RoundResult::Win), Throw::Rock);
+    assert_eq!(choose_target_hand(Throw::Scissors, RoundResult::Lose), Throw::Paper);
+}
@@ -0,0 +1,61 @@
+use aoc_common::generate::Rng;
+
+use crate::Day10Params;
+
+/// A program of `instructions` `noop` and `addx` instructions. Like the real programs, the
+/// register is kept within the width of the display so the sprite stays visible. The height of
+/// the display doesn't matter, the program draws as many frames as its cycles fill.
+pub fn program(rng: &mut impl Rng, instructions: usize, params: &Day10Params) -> String {
+    let mut program = String::new();
+    let mut register_x: isize = 1;
+
+    for _ in 0..instructions {
+        if rng.gen_ratio(1, 3) {
+            program.push_str("noop\n");
+            continue;
+        }
+
+        let target = rng.gen_range(0..params.display_width as isize);
+        let value = (target - register_x).clamp(-15, 15);
+
+        register_x += value;
+        program.push_str(&format!("addx {value}\n"));
+    }
+
+    program
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{parse_program, Cpu};
+    use aoc_common::generate::seeded;
+
+    #[test]
+    fn test_generated_program() {
+        let params = Day10Params::default();
+        let input = program(&mut seeded(10), 300, &params);
+        assert_eq!(input, program(&mut seeded(10), 300, &params));
+
+        let mut cpu = Cpu::new(parse_program(input.as_bytes()).unwrap());
+
+        while cpu.tick() {
+            assert!((0..params.display_width as isize).contains(&cpu.register_x()));
+        }
+
+        assert!(cpu.cycle() >= 300);
+    }
+
+    #[test]
+    fn test_generated_program_for_display() {
+        let params = Day10Params { display_width: 8, display_height: 2 };
+        let input = program(&mut seeded(10), 300, &params);
+
+        let mut cpu = Cpu::new(parse_program(input.as_bytes()).unwrap());
+
+        while cpu.tick() {
+            assert!((0..8).contains(&cpu.register_x()));
+        }
+    }
+}
@@ -0,0 +1,668 @@
+use std::io::BufRead;
+
+use aoc_common::diagnostics::parse_lines;
+use aoc_common::generate::InputRng;
+use aoc_common::recorder::Rgb;
+use aoc_common::{Frame, ParseError, Part, Simulation, Solution, Step};
+use geometry::{Grid, Point};
+use serde::Deserialize;
+
+pub mod generate;
+
+const DISPLAY_HEIGHT: usize = 6;
+const DISPLAY_WIDTH: usize = 40;
+
+const LIT_COLOR: Rgb = [255, 255, 255];
+const DARK_COLOR: Rgb = [24, 24, 24];
+const BEAM_COLOR: Rgb = [220, 40, 40];
+
+pub struct Day10;
+
+/// Size of the CRT the program draws to.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields, try_from = "UncheckedDay10Params")]
+pub struct Day10Params {
+    pub display_width: usize,
+    pub display_height: usize,
+}
+
+impl Default for Day10Params {
+    fn default() -> Self {
+        Day10Params {
+            display_width: DISPLAY_WIDTH,
+            display_height: DISPLAY_HEIGHT,
+        }
+    }
+}
+
+/// [`Day10Params`] as given, before checking the display has at least one pixel.
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct UncheckedDay10Params {
+    display_width: usize,
+    display_height: usize,
+}
+
+impl Default for UncheckedDay10Params {
+    fn default() -> Self {
+        let Day10Params { display_width, display_height } = Day10Params::default();
+
+        UncheckedDay10Params { display_width, display_height }
+    }
+}
+
+impl TryFrom<UncheckedDay10Params> for Day10Params {
+    type Error = String;
+
+    fn try_from(unchecked: UncheckedDay10Params) -> Result<Self, String> {
+        let UncheckedDay10Params { display_width, display_height } = unchecked;
+
+        if display_width == 0 || display_height == 0 {
+            return Err(format!(
+                "the display must be at least one pixel wide and high, not \
+                 {display_width}x{display_height}"
+            ));
+        }
+
+        Ok(Day10Params { display_width, display_height })
+    }
+}
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const DATA_DIR: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/data");
+    const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+
+    type Input = Vec<Operation>;
+    type Params = Day10Params;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        parse_program(data)
+    }
+
+    fn part_one(input: &Self::Input) -> Option<String> {
+        let mut cpu = Cpu::new(input.clone());
+
+        let signal_strengths = cpu.run_with_signal_strengths()?;
+        let signal_sum = signal_strengths.iter().try_fold(0_isize, |sum, s| sum.checked_add(*s))?;
+
+        Some(signal_sum.to_string())
+    }
+
+    /// The answer to the second part is whatever letters are drawn on the display, the rendered
+    /// display is returned for a human to read.
+    fn part_two(input: &Self::Input) -> Option<String> {
+        let mut cpu = Cpu::new(input.clone());
+        cpu.run_with_signal_strengths()?;
+
+        Some(cpu.display_string())
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        let params: Day10Params = aoc_common::params::current();
+        Some(generate::program(rng, size, &params))
+    }
+
+    fn simulate(input: &Self::Input, _part: Part) -> Option<Box<dyn Simulation>> {
+        Some(Box::new(Cpu::new(input.clone())))
+    }
+}
+
+/// The handheld's CPU, which runs a program one cycle at a time while drawing to its CRT.
+pub struct Cpu {
+    instructions: Vec<Operation>,
+
+    instruction_counter: usize,
+    cycle_counter: usize,
+
+    register_x: isize, // also the sprite position
+
+    display: Grid<bool>,
+    signal_strength: Option<isize>,
+
+    pending_cycles: Option<usize>,
+    /// Set once the register or the signal strength no longer fits, which halts the CPU.
+    overflowed: bool,
+}
+
+impl Cpu {
+    pub fn current_operation(&self) -> Option<Operation> {
+        self.instructions.get(self.instruction_counter).copied()
+    }
+
+    fn current_pixel(&self) -> Point {
+        // The cycle counter advances before we update the display, but the display is zero-indexed
+        // so we need to reduce it by one
+        let width = self.display.width();
+        let index = (self.cycle_counter - 1) % (width * self.display.height());
+
+        Point::new((index % width) as isize, (index / width) as isize)
+    }
+
+    /// Number of cycles completed so far.
+    pub fn cycle(&self) -> usize {
+        self.cycle_counter
+    }
+
+    /// Renders the display, with the pixel drawn during the last cycle highlighted.
+    pub fn frame(&self) -> Frame {
+        let beam = (self.cycle_counter > 0).then(|| self.current_pixel());
+
+        Frame::from_fn(self.display.width(), self.display.height(), |column, row| {
+            let pixel = Point::new(column as isize, row as isize);
+
+            if beam == Some(pixel) {
+                BEAM_COLOR
+            } else if self.display[pixel] {
+                LIT_COLOR
+            } else {
+                DARK_COLOR
+            }
+        })
+    }
+
+    pub fn display_string(&self) -> String {
+        self.display.render(|px| { if *px { '#' } else { '.' } })
+    }
+
+    fn in_sprite_window(&self) -> bool {
+        let Some((min, max)) = self.sprite_window() else {
+            return false;
+        };
+
+        // We only look at the horizontal position to determine if we're in the
+        // sprite window
+        let pixel_loc = self.current_pixel().x as usize;
+
+        min <= pixel_loc && pixel_loc <= max
+    }
+
+    /// Creates a CPU with a display sized by the current [`Day10Params`].
+    pub fn new(instructions: Vec<Operation>) -> Self {
+        let params: Day10Params = aoc_common::params::current();
+
+        Cpu {
+            instructions,
+
+            instruction_counter: 0,
+            cycle_counter: 0,
+
+            register_x: 1,
+
+            display: Grid::new(params.display_width, params.display_height, false),
+            signal_strength: None,
+
+            pending_cycles: None,
+            overflowed: false,
+        }
+    }
+
+    /// Cycles still needed by the current operation, `None` when the next cycle starts a new
+    /// operation.
+    pub fn pending_cycles(&self) -> Option<usize> {
+        self.pending_cycles
+    }
+
+    pub fn register_x(&self) -> isize {
+        self.register_x
+    }
+
+    /// Runs the program to completion, returning the signal strength during the 20th cycle and
+    /// every 40 cycles after that. `None` when the program overflows the register or the signal
+    /// strength before it finishes.
+    ///
+    /// When recording, a frame of the display is captured as each cycle is drawn.
+    pub fn run_with_signal_strengths(&mut self) -> Option<Vec<isize>> {
+        let recorder = aoc_common::recorder::current();
+        let mut signal_strengths = vec![];
+
+        while self.tick() {
+            if let Some(recorder) = &recorder {
+                recorder.step(|| self.frame());
+            }
+
+            if (self.cycle_counter + 20).is_multiple_of(40) {
+                let signal_strength = self.signal_strength.unwrap();
+                tracing::debug!(cycle = self.cycle_counter, signal_strength, "sampled signal");
+
+                signal_strengths.push(signal_strength);
+            }
+        }
+
+        if let Some(recorder) = &recorder {
+            recorder.finish(|| self.frame());
+        }
+
+        if self.overflowed {
+            tracing::warn!(cycle = self.cycle_counter, x = self.register_x, "program overflowed");
+            return None;
+        }
+
+        Some(signal_strengths)
+    }
+
+    /// Signal strength during the most recent cycle, `None` before the first cycle has run.
+    pub fn signal_strength(&self) -> Option<isize> {
+        self.signal_strength
+    }
+
+    /// Columns of the display covered by the three pixel wide sprite, `None` when the register
+    /// has moved the whole sprite off either edge of the display.
+    fn sprite_window(&self) -> Option<(usize, usize)> {
+        let min = self.register_x.saturating_sub(1).max(0);
+        let max = self.register_x.saturating_add(1).min(self.display.width() as isize - 1);
+
+        (min <= max).then_some((min as usize, max as usize))
+    }
+
+    /// Runs a single cycle, returning false once the program has finished or the CPU halted
+    /// because a value overflowed.
+    pub fn tick(&mut self) -> bool {
+        if self.overflowed {
+            return false;
+        }
+
+        if let Some(op) = self.current_operation() {
+            self.cycle_counter += 1;
+
+            if let Some(rem) = self.pending_cycles {
+                self.pending_cycles = Some(rem - 1);
+            } else {
+                self.pending_cycles = Some(op.cycle_count() - 1);
+            }
+
+            self.update_display();
+            self.update_signal_strength();
+
+            tracing::trace!(
+                cycle = self.cycle_counter,
+                x = self.register_x,
+                operation = ?op,
+                pending_cycles = ?self.pending_cycles,
+                "cycle"
+            );
+
+            if self.pending_cycles == Some(0) {
+                self.pending_cycles = None;
+                op.apply(self);
+                self.instruction_counter += 1;
+            }
+
+            !self.overflowed
+        } else {
+            false
+        }
+    }
+
+    fn update_display(&mut self) {
+        if self.in_sprite_window() {
+            let pixel = self.current_pixel();
+            self.display[pixel] = true;
+        }
+    }
+
+    fn update_signal_strength(&mut self) {
+        match self.register_x.checked_mul(self.cycle_counter as isize) {
+            Some(signal_strength) => self.signal_strength = Some(signal_strength),
+            None => self.overflowed = true,
+        }
+    }
+}
+
+impl std::fmt::Debug for Cpu {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cpu")
+            .field("instruction_counter", &self.instruction_counter)
+            .field("cycle_counter", &self.cycle_counter)
+            .field("register_x", &self.register_x)
+            .field("current_operation", &self.current_operation())
+            .field("pending_cycles", &self.pending_cycles)
+            .field("signal_strength", &self.signal_strength)
+            .finish()
+    }
+}
+
+/// Each step is a cycle, finishing an operation is a milestone.
+impl Simulation for Cpu {
+    fn step(&mut self) -> Step {
+        if !self.tick() {
+            Step::Finished
+        } else if self.pending_cycles.is_none() {
+            Step::Milestone
+        } else {
+            Step::Running
+        }
+    }
+
+    fn frame(&self) -> Frame {
+        Cpu::frame(self)
+    }
+
+    fn state(&self) -> Vec<(&'static str, String)> {
+        let optional = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+
+        vec![
+            ("cycle", self.cycle_counter.to_string()),
+            ("x", self.register_x.to_string()),
+            ("operation", optional(self.current_operation().map(|o| format!("{o:?}")))),
+            ("pending cycles", optional(self.pending_cycles.map(|c| c.to_string()))),
+            ("signal strength", optional(self.signal_strength.map(|s| s.to_string()))),
+        ]
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Operation {
+    Noop,
+    AddX(isize),
+}
+
+impl Operation {
+    fn apply(&self, cpu: &mut Cpu) {
+        use Operation::*;
+
+        if let AddX(val) = self {
+            match cpu.register_x.checked_add(*val) {
+                Some(register_x) => cpu.register_x = register_x,
+                None => cpu.overflowed = true,
+            }
+        }
+    }
+
+    /// Number of cycles the operation takes to complete.
+    pub fn cycle_count(&self) -> usize {
+        use Operation::*;
+
+        match self {
+            Noop => 1,
+            AddX(_) => 2,
+        }
+    }
+}
+
+impl TryFrom<&str> for Operation {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Operation, ParseError> {
+        use Operation::*;
+
+        let instruction: Vec<&str> = value.split_whitespace().collect();
+        match instruction[..] {
+            ["noop"] => Ok(Noop),
+            ["addx", val] => val
+                .parse()
+                .map(AddX)
+                .map_err(|_| ParseError::at(value, val, "expected a number to add")),
+            ["noop", extra, ..] => Err(ParseError::at(value, extra, "noop doesn't take an operand")),
+            ["addx"] => Err(ParseError::spanning(
+                value,
+                value.len()..value.len(),
+                "addx requires a number to add",
+            )),
+            ["addx", _, extra, ..] => Err(ParseError::at(value, extra, "unexpected extra operand")),
+            [unknown, ..] => Err(ParseError::at(
+                value,
+                unknown,
+                format!("unknown instruction {:?}", unknown),
+            )),
+            [] => Err(ParseError::whole_line(value, "expected an instruction")),
+        }
+    }
+}
+
+/// Reads the program one instruction per line, from memory or streamed from a file.
+pub fn parse_program<R: BufRead>(reader: R) -> Result<Vec<Operation>, ParseError> {
+    parse_lines(reader, |line| Operation::try_from(line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIN_SAMPLE: &[u8] = "noop\naddx 3\naddx -5".as_bytes();
+
+    const SAMPLE_INPUT: &[u8] = include_bytes!("../data/sample");
+
+    #[test]
+    fn test_cpu_running() {
+        use Operation::*;
+
+        let mut cpu = Cpu::new(vec![Noop, AddX(3), AddX(-5)]);
+
+        // Check the initial state
+        assert_eq!(cpu.current_operation(), Some(Noop));
+        assert_eq!(cpu.cycle_counter, 0);
+        assert_eq!(cpu.register_x, 1);
+
+        // Check the state after the first cycle
+        assert!(cpu.tick());
+        assert_eq!(cpu.current_operation(), Some(AddX(3)));
+        assert_eq!(cpu.cycle_counter, 1);
+        assert_eq!(cpu.register_x, 1);
+
+        // Check the state after the second cycle
+        assert!(cpu.tick());
+        assert_eq!(cpu.current_operation(), Some(AddX(3)));
+        assert_eq!(cpu.cycle_counter, 2);
+        assert_eq!(cpu.register_x, 1);
+
+        // Third cycle
+        assert!(cpu.tick());
+        assert_eq!(cpu.current_operation(), Some(AddX(-5)));
+        assert_eq!(cpu.cycle_counter, 3);
+        assert_eq!(cpu.register_x, 4);
+
+        // Fourth cycle
+        assert!(cpu.tick());
+        assert_eq!(cpu.current_operation(), Some(AddX(-5)));
+        assert_eq!(cpu.cycle_counter, 4);
+        assert_eq!(cpu.register_x, 4);
+
+        // Fifth cycle
+        assert!(cpu.tick());
+        assert_eq!(cpu.current_operation(), None);
+        assert_eq!(cpu.cycle_counter, 5);
+        assert_eq!(cpu.register_x, -1);
+
+        // All future ticks
+        assert!(!cpu.tick());
+        assert_eq!(cpu.cycle_counter, 5);
+    }
+
+    #[test]
+    fn test_full_sample_parsing() {
+        use Operation::*;
+
+        let program = parse_program(MIN_SAMPLE).unwrap();
+        assert_eq!(vec![Noop, AddX(3), AddX(-5)], program);
+    }
+
+    #[test]
+    fn test_min_sample_program() {
+        let program = parse_program(SAMPLE_INPUT).unwrap();
+        let mut cpu = Cpu::new(program);
+        let signal_strengths = cpu.run_with_signal_strengths().unwrap();
+
+        assert_eq!(6, signal_strengths.len());
+        assert_eq!(vec![420, 1140, 1800, 2940, 2880, 3960], signal_strengths);
+
+        let signal_sum: isize = signal_strengths.iter().sum();
+        assert_eq!(13140, signal_sum);
+    }
+
+    #[test]
+    fn test_sprite_window() {
+        let mut cpu = Cpu::new(vec![]);
+
+        assert_eq!(1, cpu.register_x);
+        assert_eq!(Some((0, 2)), cpu.sprite_window());
+
+        cpu.register_x = 0;
+        assert_eq!(Some((0, 1)), cpu.sprite_window());
+
+        cpu.register_x = (DISPLAY_WIDTH - 1) as isize;
+        assert_eq!(Some((38, 39)), cpu.sprite_window());
+    }
+
+    #[test]
+    fn test_sprite_window_off_the_display() {
+        let mut cpu = Cpu::new(vec![]);
+
+        cpu.register_x = -1;
+        assert_eq!(Some((0, 0)), cpu.sprite_window());
+
+        cpu.register_x = DISPLAY_WIDTH as isize;
+        assert_eq!(Some((39, 39)), cpu.sprite_window());
+
+        for register_x in [-2, -100, isize::MIN, DISPLAY_WIDTH as isize + 1, isize::MAX] {
+            cpu.register_x = register_x;
+            assert_eq!(None, cpu.sprite_window());
+            assert!(!cpu.in_sprite_window());
+        }
+    }
+
+    #[test]
+    fn test_current_pixel() {
+        let mut cpu = Cpu::new(vec![]);
+
+        cpu.cycle_counter = 56;
+        assert_eq!(Point::new(15, 1), cpu.current_pixel());
+
+        cpu.cycle_counter = DISPLAY_WIDTH * DISPLAY_HEIGHT;
+        assert_eq!(Point::new(39, 5), cpu.current_pixel());
+
+        cpu.cycle_counter = DISPLAY_WIDTH * DISPLAY_HEIGHT + 43;
+        assert_eq!(Point::new(2, 1), cpu.current_pixel());
+    }
+
+    #[test]
+    fn test_in_sprite_window() {
+        let mut cpu = Cpu::new(vec![]);
+
+        cpu.cycle_counter = 24;
+
+        cpu.register_x = 21;
+        assert!(!cpu.in_sprite_window());
+
+        cpu.register_x = 22;
+        assert!(cpu.in_sprite_window());
+
+        cpu.register_x = 23;
+        assert!(cpu.in_sprite_window());
+
+        cpu.register_x = 24;
+        assert!(cpu.in_sprite_window());
+
+        cpu.register_x = 25;
+        assert!(!cpu.in_sprite_window());
+    }
+
+    #[test]
+    fn test_display_rendering() {
+        let mut cpu = Cpu::new(vec![]);
+
+        cpu.display[Point::new(0, 0)] = true;
+        aoc_common::assert_snapshot!("display_top_left", cpu.display_string());
+
+        cpu.display[Point::new(39, 0)] = true;
+        aoc_common::assert_snapshot!("display_top_corners", cpu.display_string());
+
+        cpu.display[Point::new(0, 5)] = true;
+        aoc_common::assert_snapshot!("display_three_corners", cpu.display_string());
+
+        cpu.display[Point::new(39, 5)] = true;
+        aoc_common::assert_snapshot!("display_all_corners", cpu.display_string());
+    }
+
+    #[test]
+    fn test_sample_display_output() {
+        let program = parse_program(SAMPLE_INPUT).unwrap();
+
+        let mut cpu = Cpu::new(program);
+        cpu.run_with_signal_strengths();
+
+        assert_eq!(cpu.cycle_counter, 240);
+        aoc_common::assert_snapshot!("sample_display", cpu.display_string());
+    }
+
+    #[test]
+    fn test_display_params() {
+        let params = aoc_common::Params::new([
+            ("display_width".to_string(), 8.into()),
+            ("display_height".to_string(), 2.into()),
+        ]);
+
+        let mut cpu = params.scope(|| Cpu::new(parse_program(SAMPLE_INPUT).unwrap()));
+        cpu.run_with_signal_strengths();
+
+        // The beam wraps around the smaller display, so later rows draw over the earlier ones
+        let display = cpu.display_string();
+        assert_eq!(display.lines().map(str::len).collect::<Vec<_>>(), vec![8, 8]);
+    }
+
+    #[test]
+    fn test_recorded_frames() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let recorder = aoc_common::Recorder::new(40, sender);
+
+        let mut cpu = Cpu::new(parse_program(SAMPLE_INPUT).unwrap());
+        recorder.scope(|| cpu.run_with_signal_strengths());
+
+        // Every 40 cycles from the first, along with the final display
+        let frames: Vec<Frame> = receiver.try_iter().collect();
+        assert_eq!(frames.len(), 7);
+
+        assert_eq!(frames[0].pixel(0, 0), BEAM_COLOR);
+        assert_eq!(frames[0].pixel(1, 0), DARK_COLOR);
+
+        let last = frames.last().unwrap();
+        assert_eq!(last.pixel(0, 0), LIT_COLOR);
+        assert_eq!(last.pixel(DISPLAY_WIDTH - 1, DISPLAY_HEIGHT - 1), BEAM_COLOR);
+    }
+
+    #[test]
+    fn test_simulation_steps() {
+        let program = parse_program(MIN_SAMPLE).unwrap();
+        let mut simulation = Day10::simulate(&program, Part::One).unwrap();
+
+        let steps: Vec<Step> = std::iter::from_fn(|| Some(simulation.step()))
+            .take_while(|step| *step != Step::Finished)
+            .collect();
+
+        // Every operation finishing is a milestone, the addx operations take two cycles
+        assert_eq!(
+            steps,
+            vec![Step::Milestone, Step::Running, Step::Milestone, Step::Running, Step::Milestone]
+        );
+        assert!(simulation.state().contains(&("x", "-1".to_string())));
+    }
+
+    #[test]
+    fn test_invalid_program() {
+        let err = parse_program(&b"noop\naddx five\n"[..]).unwrap_err();
+        assert_eq!(err.line_number(), 2);
+        assert_eq!(err.columns(), 6..10);
+
+        let err = parse_program(&b"noop\nnoop\nmulx 3\n"[..]).unwrap_err();
+        assert_eq!(err.line_number(), 3);
+        assert_eq!(err.message(), "unknown instruction \"mulx\"");
+    }
+
+    #[test]
+    fn test_overflowing_program() {
+        use Operation::*;
+
+        // The register itself overflows
+        let program = vec![Noop, AddX(isize::MAX), Noop];
+        assert_eq!(Day10::part_one(&program), None);
+        assert_eq!(Day10::part_two(&program), None);
+
+        // The register fits but the signal strength doesn't
+        let program = [vec![AddX(isize::MAX / 4)], vec![Noop; 20]].concat();
+        assert_eq!(Day10::part_one(&program), None);
+
+        let mut cpu = Cpu::new(program);
+        while cpu.tick() {}
+        assert_eq!(cpu.cycle(), 4);
+    }
+}
@@ -0,0 +1,59 @@
+use aoc_common::generate::seeded;
+use aoc_common::Solution;
+use day_10::{generate, parse_program, Cpu, Day10, Day10Params};
+use proptest::prelude::*;
+
+/// Solves both parts whenever the data parses, neither may panic.
+fn solve(data: &[u8]) {
+    if let Ok(program) = Day10::parse(data) {
+        let _ = Day10::part_one(&program);
+        let _ = Day10::part_two(&program);
+    }
+}
+
+proptest! {
+    #[test]
+    fn test_arbitrary_bytes_never_panic(data in prop::collection::vec(any::<u8>(), 0..256)) {
+        solve(&data);
+    }
+
+    #[test]
+    fn test_arbitrary_instructions_never_panic(data in "((noop|addx -?[0-9]{1,20}| ?addx ?)\n){0,8}") {
+        solve(data.as_bytes());
+    }
+
+    #[test]
+    fn test_large_operands_never_panic(operands in prop::collection::vec(any::<i64>(), 0..30)) {
+        let program: String = operands.iter().map(|operand| format!("addx {operand}\nnoop\n")).collect();
+        solve(program.as_bytes());
+    }
+
+    #[test]
+    fn test_sprite_off_the_display_draws_nothing(
+        register_x in prop_oneof![-1000isize..=-2, 41isize..1000],
+        noops in 0..300usize,
+    ) {
+        // The first two cycles are drawn with the register at 1, before the addx moves the sprite
+        let program = format!("addx {}\n{}", register_x - 1, "noop\n".repeat(noops));
+        let mut cpu = Cpu::new(parse_program(program.as_bytes()).unwrap());
+        cpu.run_with_signal_strengths().unwrap();
+
+        prop_assert_eq!(cpu.register_x(), register_x);
+        prop_assert_eq!(cpu.display_string().matches('#').count(), 2);
+    }
+
+    #[test]
+    fn test_every_operation_takes_its_cycles(seed in any::<u64>(), instructions in 0..300usize) {
+        let input = generate::program(&mut seeded(seed), instructions, &Day10Params::default());
+        let program = parse_program(input.as_bytes()).unwrap();
+
+        let expected_cycles: usize = program.iter().map(|op| op.cycle_count()).sum();
+        let mut cpu = Cpu::new(program);
+        let signal_strengths = cpu.run_with_signal_strengths().unwrap();
+
+        prop_assert_eq!(cpu.cycle(), expected_cycles);
+
+        // Signal strengths are sampled during the 20th cycle and every 40 cycles after that
+        prop_assert_eq!(signal_strengths.len(), (expected_cycles + 20) / 40);
+    }
+}
@@ -0,0 +1,32 @@
+use day_10::{parse_program, Cpu, Operation};
+
+const SAMPLE_INPUT: &[u8] = include_bytes!("../data/sample");
+
+#[test]
+fn test_sample_signal_strengths() {
+    let program = parse_program(SAMPLE_INPUT).unwrap();
+    assert_eq!(program[0], Operation::AddX(15));
+
+    let mut cpu = Cpu::new(program);
+    let signal_strengths = cpu.run_with_signal_strengths().unwrap();
+
+    assert_eq!(signal_strengths, vec![420, 1140, 1800, 2940, 2880, 3960]);
+    assert_eq!(signal_strengths.iter().sum::<isize>(), 13140);
+    assert!(cpu.display_string().starts_with("##..##..##..##..##..##..##..##..##..##.."));
+}
+
+#[test]
+fn test_stepping_the_cpu() {
+    let mut cpu = Cpu::new(vec![Operation::Noop, Operation::AddX(3)]);
+    assert_eq!(cpu.signal_strength(), None);
+
+    assert!(cpu.tick());
+    assert!(cpu.tick());
+    assert_eq!(cpu.pending_cycles(), Some(1));
+    assert_eq!(cpu.signal_strength(), Some(2));
+
+    assert!(cpu.tick());
+    assert_eq!(cpu.cycle(), 3);
+    assert_eq!(cpu.register_x(), 4);
+    assert!(!cpu.tick());
+}
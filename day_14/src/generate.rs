@@ -0,0 +1,69 @@
+use aoc_common::generate::Rng;
+
+use crate::SPAWNER_X;
+
+/// Rock paths are kept to the same area of the cave as the real scans, a little below and to
+/// either side of the spawner. That keeps the pile of sand on the floor within the simulation.
+const MAX_X_OFFSET: isize = 60;
+const MIN_Y: isize = 10;
+const MAX_Y: isize = 160;
+
+/// A scan of `paths` rock paths, each made of between one and five horizontal or vertical
+/// segments.
+pub fn rock_paths(rng: &mut impl Rng, paths: usize) -> String {
+    let (min_x, max_x) = (SPAWNER_X - MAX_X_OFFSET, SPAWNER_X + MAX_X_OFFSET);
+    let mut scan = String::new();
+
+    for _ in 0..paths {
+        let mut x = rng.gen_range(min_x..=max_x);
+        let mut y = rng.gen_range(MIN_Y..=MAX_Y);
+        let mut points = vec![format!("{x},{y}")];
+
+        let mut horizontal = rng.gen_bool(0.5);
+
+        for _ in 0..rng.gen_range(1..=5) {
+            let length = rng.gen_range(1..=8) * if rng.gen_bool(0.5) { 1 } else { -1 };
+
+            // Segments that would leave the area are turned around instead of clipped so they
+            // never end up with no length
+            if horizontal {
+                x = if (min_x..=max_x).contains(&(x + length)) { x + length } else { x - length };
+            } else {
+                y = if (MIN_Y..=MAX_Y).contains(&(y + length)) { y + length } else { y - length };
+            }
+
+            points.push(format!("{x},{y}"));
+            horizontal = !horizontal;
+        }
+
+        scan.push_str(&points.join(" -> "));
+        scan.push('\n');
+    }
+
+    scan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::parse_simulated_environment;
+    use aoc_common::generate::seeded;
+
+    #[test]
+    fn test_generated_rock_paths() {
+        let input = rock_paths(&mut seeded(14), 50);
+        assert_eq!(input, rock_paths(&mut seeded(14), 50));
+        assert_eq!(input.lines().count(), 50);
+
+        let mut sim_env = parse_simulated_environment(input.as_bytes()).unwrap();
+        sim_env.tick_till_done().unwrap();
+
+        let resting_sand = sim_env.count_resting_sand();
+
+        // The floor catches all of the sand that fell past the rocks
+        sim_env.add_floor();
+        sim_env.tick_till_done().unwrap();
+        assert!(sim_env.count_resting_sand() > resting_sand);
+    }
+}
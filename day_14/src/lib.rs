@@ -0,0 +1,810 @@
+use std::io::BufRead;
+use std::ops::Range;
+
+use aoc_common::diagnostics::for_each_line;
+use aoc_common::generate::InputRng;
+use aoc_common::recorder::Rgb;
+use aoc_common::{Frame, Interrupted, ParseError, Part, Recorder, Simulation, Solution, Step};
+use geometry::{Grid, LineSegment, Point, Rect};
+use serde::{Deserialize, Serialize};
+
+pub mod generate;
+
+const SIMULATION_HEIGHT: usize = 1024;
+const SIMULATION_WIDTH: usize = 1024;
+
+const SPAWNER_X: isize = 500;
+const SPAWNER_Y: isize = 0;
+
+const SEARCH_OFFSETS: [(isize, isize); 3] = [
+    (0, 1), (-1, 1), (1, 1),
+];
+
+pub struct Day14;
+
+/// Where the sand comes from and the size of the cave the simulation covers. The cave has to be
+/// wide enough for the pile of sand on the floor to fit, or the edges stop it early.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields, try_from = "UncheckedDay14Params")]
+pub struct Day14Params {
+    pub spawner_x: isize,
+    pub spawner_y: isize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Day14Params {
+    /// The area rock may be placed in, leaving a column on either side for sand to fall past the
+    /// outermost rocks and room at the bottom for the floor two rows below the lowest rock.
+    fn rock_area(&self) -> (Range<isize>, Range<isize>) {
+        (1..(self.width as isize - 1), 0..(self.height as isize - 2))
+    }
+}
+
+impl Default for Day14Params {
+    fn default() -> Self {
+        Day14Params {
+            spawner_x: SPAWNER_X,
+            spawner_y: SPAWNER_Y,
+            width: SIMULATION_WIDTH,
+            height: SIMULATION_HEIGHT,
+        }
+    }
+}
+
+/// [`Day14Params`] as given, before checking the spawner is within the simulated area.
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct UncheckedDay14Params {
+    spawner_x: isize,
+    spawner_y: isize,
+    width: usize,
+    height: usize,
+}
+
+impl Default for UncheckedDay14Params {
+    fn default() -> Self {
+        let Day14Params { spawner_x, spawner_y, width, height } = Day14Params::default();
+
+        UncheckedDay14Params { spawner_x, spawner_y, width, height }
+    }
+}
+
+impl TryFrom<UncheckedDay14Params> for Day14Params {
+    type Error = String;
+
+    fn try_from(unchecked: UncheckedDay14Params) -> Result<Self, String> {
+        let UncheckedDay14Params { spawner_x, spawner_y, width, height } = unchecked;
+        let params = Day14Params { spawner_x, spawner_y, width, height };
+
+        let (x_range, y_range) = params.rock_area();
+        if !x_range.contains(&spawner_x) || !y_range.contains(&spawner_y) {
+            return Err(format!(
+                "the spawner at {spawner_x},{spawner_y} is outside of the simulated area \
+                 ({x_range:?}, {y_range:?})"
+            ));
+        }
+
+        Ok(params)
+    }
+}
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    const DATA_DIR: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/data");
+    const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+
+    type Input = SimulatedEnvironment;
+    type Params = Day14Params;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        parse_simulated_environment(data)
+    }
+
+    fn part_one(input: &Self::Input) -> Option<String> {
+        let mut sim_env = input.clone();
+
+        sim_env.tick_till_done().ok()?;
+
+        Some(sim_env.count_resting_sand().to_string())
+    }
+
+    fn part_two(input: &Self::Input) -> Option<String> {
+        let mut sim_env = input.clone();
+
+        sim_env.add_floor();
+        sim_env.tick_till_done().ok()?;
+
+        Some(sim_env.count_resting_sand().to_string())
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generate::rock_paths(rng, size))
+    }
+
+    fn simulate(input: &Self::Input, part: Part) -> Option<Box<dyn Simulation>> {
+        let mut sim_env = input.clone();
+
+        if part == Part::Two {
+            sim_env.add_floor();
+        }
+
+        Some(Box::new(sim_env))
+    }
+}
+
+#[derive(Clone)]
+pub struct SimulatedEnvironment {
+    aabb: Rect,
+    active_sand: Option<Point>,
+    spawner_location: Point,
+
+    has_floor: bool,
+    path_tracing: bool,
+
+    tiles: Grid<Tile>,
+}
+
+impl SimulatedEnvironment {
+    /// Adds the infinite floor two below the lowest rock, as the second part requires.
+    pub fn add_floor(&mut self) {
+        self.has_floor = true;
+
+        let floor_height = self.aabb.max.y + 2;
+        self.aabb.max.y = floor_height;
+
+        tracing::debug!(y = floor_height, "added the floor");
+
+        for x in 0..self.tiles.width() {
+            self.set_tile(x as isize, floor_height, Tile::Rock);
+        }
+    }
+
+    fn checkpoint_state(&self) -> SandCheckpoint {
+        let resting_sand = self.tiles.iter()
+            .filter(|(_, tile)| **tile == Tile::Sand(false))
+            .map(|(point, _)| (point.x, point.y))
+            .collect();
+
+        SandCheckpoint {
+            has_floor: self.has_floor,
+            resting_sand,
+        }
+    }
+
+    /// The grain currently falling, if any.
+    pub fn active_sand(&self) -> Option<Point> {
+        self.active_sand
+    }
+
+    pub fn count_resting_sand(&self) -> usize {
+        self.tiles.cells().filter(|t| Tile::Sand(false) == **t).count()
+    }
+
+    pub fn display_string(&self) -> String {
+        self.tiles.region(self.aabb)
+            .expect("the simulated area is always within the tiles")
+            .render(Tile::char)
+    }
+
+    /// Draws a segment of a rock path. Rock paths are only ever horizontal or vertical, which
+    /// [`parse_line`] checks before any of their segments are drawn.
+    fn draw_line_segment(&mut self, line: LineSegment) {
+        debug_assert!(line.horizontal() || line.vertical(), "diagonal rock path: {line:?}");
+
+        self.aabb = self.aabb.union(&line.aabb());
+
+        for point in line.points() {
+            self.set_tile(point.x, point.y, Tile::Rock);
+        }
+    }
+
+    /// Renders the area of the cave the simulation can cover, which is the same in every frame so
+    /// the frames of a recording line up.
+    pub fn frame(&self) -> Frame {
+        let area = self.reachable_area();
+        let origin = area.min;
+
+        Frame::from_fn(area.width(), area.height(), |column, row| {
+            self.get_tile(origin.x + column as isize, origin.y + row as isize).color()
+        })
+        .with_origin(origin.x, origin.y)
+    }
+
+    pub fn enable_path_tracing(&mut self) {
+        self.path_tracing = true;
+    }
+
+    /// The tile at the location, anything beyond the edges of the simulation is solid rock. The
+    /// simulation is wide enough that only sand piled up on the floor below very deep rocks can
+    /// reach the edges.
+    fn get_tile(&self, x: isize, y: isize) -> Tile {
+        self.tiles.get(Point::new(x, y)).copied().unwrap_or(Tile::Rock)
+    }
+
+    fn new(params: &Day14Params) -> Self {
+        let spawner_location = Point::new(params.spawner_x, params.spawner_y);
+
+        let mut sim_env = SimulatedEnvironment {
+            aabb: Rect::from_point(spawner_location),
+            active_sand: None,
+            spawner_location,
+
+            has_floor: false,
+            path_tracing: false,
+
+            tiles: Grid::new(params.width, params.height, Tile::default()),
+        };
+
+        sim_env.set_tile(spawner_location.x, spawner_location.y, Tile::Spawner);
+
+        sim_env
+    }
+
+    /// The area sand can reach. Without a floor any sand leaving the area around the rocks falls
+    /// out of the simulation, with one the sand piles up into a triangle below the spawner that
+    /// spreads out one column either way for every row it falls.
+    fn reachable_area(&self) -> Rect {
+        if !self.has_floor {
+            return self.aabb;
+        }
+
+        let depth = self.aabb.max.y - self.spawner_location.y;
+        let pile = Rect::new(
+            self.spawner_location.offset(-depth, 0),
+            self.spawner_location.offset(depth, depth),
+        );
+
+        self.aabb.union(&pile)
+    }
+
+    /// Places the resting sand from a checkpoint back into the simulation, returning the number of
+    /// grains restored. Checkpoints for a different part or with sand outside of the simulated
+    /// area are ignored.
+    fn restore(&mut self, state: &SandCheckpoint) -> usize {
+        let in_bounds = |&(x, y): &(isize, isize)| self.tiles.contains(Point::new(x, y));
+
+        if state.has_floor != self.has_floor || !state.resting_sand.iter().all(in_bounds) {
+            return 0;
+        }
+
+        tracing::info!(grains = state.resting_sand.len(), "restoring resting sand from checkpoint");
+
+        for &(x, y) in state.resting_sand.iter() {
+            if self.has_floor {
+                self.aabb = self.aabb.include(Point::new(x, y));
+            }
+
+            self.set_tile(x, y, Tile::Sand(false));
+        }
+
+        state.resting_sand.len()
+    }
+
+    fn set_tile(&mut self, x: isize, y: isize, tile: Tile) {
+        if self.spawner_location.x == x && self.spawner_location.y == y && tile != Tile::Spawner {
+            tracing::warn!(x, y, ?tile, "attempted overwriting of the spawner location");
+            return;
+        }
+
+        self.tiles[Point::new(x, y)] = tile;
+    }
+
+    /// Moves the falling grain one step, spawning a new grain when none is falling. Returns
+    /// whether the grain moved, or `None` once the simulation is done.
+    pub fn tick(&mut self) -> Option<bool> {
+        if let Some(sand) = self.active_sand {
+            let next_loc = SEARCH_OFFSETS.iter()
+                .map(|(ox, oy)| (sand.x + ox, sand.y + oy))
+                .find(|(x, y)| { self.get_tile(*x, *y).is_empty() });
+
+            if let Some((new_x, new_y)) = next_loc {
+                if self.has_floor {
+                    self.aabb = self.aabb.include(Point::new(new_x, new_y));
+                }
+
+                let new_blank_tile = if self.path_tracing {
+                    Tile::Path
+                } else {
+                    Tile::Empty
+                };
+
+                self.set_tile(sand.x, sand.y, new_blank_tile);
+
+                if !self.aabb.contains(Point::new(new_x, new_y)) {
+                    // Sand left our active map, that's our completion status, mark it the last
+                    // valid place we were at, clean up a bit and exit
+                    tracing::debug!(x = new_x, y = new_y, "sand fell out of the simulated area");
+
+                    self.active_sand = None;
+                    return None;
+                }
+
+                self.set_tile(new_x, new_y, Tile::Sand(true));
+                self.active_sand = Some(Point::new(new_x, new_y));
+
+                Some(true)
+            } else {
+                // Sand didn't move, it'll stay here
+                tracing::trace!(x = sand.x, y = sand.y, "sand came to rest");
+
+                self.active_sand = None;
+                self.set_tile(sand.x, sand.y, Tile::Sand(false));
+
+                Some(false)
+            }
+        } else {
+            let next_loc = SEARCH_OFFSETS.iter()
+                .map(|(ox, oy)| (self.spawner_location.x + ox, self.spawner_location.y + oy))
+                .find(|(x, y)| { self.get_tile(*x, *y).is_empty() });
+
+            if let Some((sx, sy)) = next_loc {
+                self.active_sand = Some(Point::new(sx, sy));
+                self.set_tile(sx, sy, Tile::Sand(true));
+
+                Some(true)
+            } else {
+                // We can't spawn a new moving sand tile at the target location exit early. The
+                // grain that would have been spawned comes to rest on top of the spawner itself.
+                let (x, y) = (self.spawner_location.x, self.spawner_location.y);
+                tracing::debug!(x, y, "stopped since we're unable to spawn new sand");
+
+                self.tiles[self.spawner_location] = Tile::Sand(false);
+
+                None
+            }
+        }
+    }
+
+    /// Ticks until the next grain comes to rest, returning false once the simulation is done.
+    pub fn tick_one_sand(&mut self) -> bool {
+        self.drop_grain(None)
+    }
+
+    /// Drops the next grain like [`SimulatedEnvironment::tick_one_sand`], reporting every tick to
+    /// the recorder.
+    fn drop_grain(&mut self, recorder: Option<&Recorder>) -> bool {
+        loop {
+            let moved = self.tick();
+
+            if let Some(recorder) = recorder {
+                recorder.step(|| self.frame());
+            }
+
+            match moved {
+                Some(true) => (),                   // sand moved, keep ticking
+                Some(false) => { return true; },    // sand didn't move but found a resting place, this method is done
+                None => { return false; },          // sand went out of bounds or was unable to spawn, the sim is done
+            }
+        }
+    }
+
+    /// Drops grains of sand until the simulation can't continue, reporting the number of grains
+    /// dropped so far to the current progress handle as it goes.
+    ///
+    /// When a checkpoint is available the resting sand is saved periodically and when the
+    /// simulation is interrupted, a later run picks up from the saved sand instead of starting
+    /// over. When recording, a frame of the cave is captured as each tick moves the sand.
+    pub fn tick_till_done(&mut self) -> Result<(), Interrupted> {
+        let progress = aoc_common::progress::current();
+        let checkpoint = aoc_common::checkpoint::current();
+        let recorder = aoc_common::recorder::current();
+
+        let mut grains_dropped = checkpoint.as_ref()
+            .and_then(|c| c.load::<SandCheckpoint>())
+            .map_or(0, |state| self.restore(&state) as u64);
+
+        while self.drop_grain(recorder.as_ref()) {
+            grains_dropped += 1;
+
+            if let Some(checkpoint) = &checkpoint {
+                checkpoint.save_if_due(|| self.checkpoint_state());
+            }
+
+            if let Err(interrupted) = progress.update("grains dropped", grains_dropped, None) {
+                if let Some(checkpoint) = &checkpoint {
+                    checkpoint.save(&self.checkpoint_state());
+                }
+
+                if let Some(recorder) = &recorder {
+                    recorder.finish(|| self.frame());
+                }
+
+                return Err(interrupted);
+            }
+        }
+
+        if let Some(checkpoint) = &checkpoint {
+            checkpoint.clear();
+        }
+
+        if let Some(recorder) = &recorder {
+            recorder.finish(|| self.frame());
+        }
+
+        Ok(())
+    }
+
+}
+
+impl std::fmt::Debug for SimulatedEnvironment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SimulatedEnvironment")
+            .field("aabb", &self.aabb)
+            .field("active_sand", &self.active_sand)
+            .field("spawner_location", &self.spawner_location)
+            .field("has_floor", &self.has_floor)
+            .field("path_tracing", &self.path_tracing)
+            .finish()
+    }
+}
+
+/// Each step is a tick, a grain coming to rest is a milestone.
+impl Simulation for SimulatedEnvironment {
+    fn step(&mut self) -> Step {
+        match self.tick() {
+            Some(true) => Step::Running,
+            Some(false) => Step::Milestone,
+            None => Step::Finished,
+        }
+    }
+
+    fn frame(&self) -> Frame {
+        SimulatedEnvironment::frame(self)
+    }
+
+    fn state(&self) -> Vec<(&'static str, String)> {
+        let active_sand = self.active_sand
+            .map_or_else(|| "-".to_string(), |sand| format!("{},{}", sand.x, sand.y));
+
+        vec![
+            ("active sand", active_sand),
+            ("resting sand", self.count_resting_sand().to_string()),
+            ("floor", self.has_floor.to_string()),
+        ]
+    }
+}
+
+/// The saved state of an interrupted [`SimulatedEnvironment::tick_till_done`]. The rocks come from
+/// the input so only the sand that has come to rest needs to be kept.
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct SandCheckpoint {
+    has_floor: bool,
+    resting_sand: Vec<(isize, isize)>,
+}
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+enum Tile {
+    #[default]
+    Empty,
+    Path,
+    Rock,
+    Sand(bool),
+    Spawner,
+}
+
+impl Tile {
+    fn char(&self) -> char {
+        use Tile::*;
+
+        match self {
+            Empty => '.',
+            Path => '~',
+            Rock => '#',
+            Sand(active) => { if *active { 'A' } else { 'o' } },
+            Spawner => '+',
+        }
+    }
+
+    fn color(&self) -> Rgb {
+        use Tile::*;
+
+        match self {
+            Empty => [16, 16, 24],
+            Path => [60, 60, 90],
+            Rock => [128, 128, 128],
+            Sand(active) => { if *active { [255, 140, 0] } else { [230, 200, 90] } },
+            Spawner => [200, 40, 40],
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        use Tile::*;
+
+        matches!(self, Empty | Path)
+    }
+}
+
+fn parse_line(data: &str, params: &Day14Params) -> Result<Vec<LineSegment>, ParseError> {
+    let points = data.split(" -> ")
+        .map(|p| {
+            let point: Point = p.parse().map_err(|msg| ParseError::at(data, p, msg))?;
+
+            let (x_range, y_range) = params.rock_area();
+
+            if !x_range.contains(&point.x) || !y_range.contains(&point.y) {
+                let msg = format!("point is outside of the simulated area ({:?}, {:?})", x_range, y_range);
+                return Err(ParseError::at(data, p, msg));
+            }
+
+            Ok((p, point))
+        })
+        .collect::<Result<Vec<(&str, Point)>, ParseError>>()?;
+
+    points.windows(2)
+        .map(|pts| {
+            let line = LineSegment::new(pts[0].1, pts[1].1);
+
+            if line.horizontal() || line.vertical() {
+                Ok(line)
+            } else {
+                Err(ParseError::between(data, pts[0].0, pts[1].0, "rock paths must be horizontal or vertical"))
+            }
+        })
+        .collect()
+}
+
+/// Reads the rock paths into a cave sized by the current [`Day14Params`], from memory or streamed
+/// from a file. Each path is drawn as soon as it's read so only the cave is kept in memory.
+pub fn parse_simulated_environment<R: BufRead>(
+    reader: R,
+) -> Result<SimulatedEnvironment, ParseError> {
+    let params: Day14Params = aoc_common::params::current();
+    let mut simulated_environment = SimulatedEnvironment::new(&params);
+
+    for_each_line(reader, |line| {
+        parse_line(line, &params)?
+            .into_iter()
+            .for_each(|l| simulated_environment.draw_line_segment(l));
+
+        Ok(())
+    })?;
+
+    Ok(simulated_environment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &[u8] = include_bytes!("../data/sample");
+
+    fn quad(left_x: isize, left_y: isize, right_x: isize, right_y: isize) -> LineSegment {
+        LineSegment::new(Point::new(left_x, left_y), Point::new(right_x, right_y))
+    }
+
+    #[test]
+    fn test_line_parsing() {
+        let data = std::str::from_utf8(SAMPLE_INPUT).unwrap();
+
+        let params = Day14Params::default();
+        let line_segments: Vec<LineSegment> = data.lines().flat_map(|l| parse_line(l, &params).unwrap()).collect();
+        let expected_line_segments = vec![
+            // first line
+            quad(498, 4, 498, 6),
+            quad(498, 6, 496, 6),
+
+            // second line
+            quad(503, 4, 502, 4),
+            quad(502, 4, 502, 9),
+            quad(502, 9, 494, 9),
+        ];
+
+        assert_eq!(line_segments, expected_line_segments);
+    }
+
+    #[test]
+    fn test_simulation_parsing() {
+        let sim_env = parse_simulated_environment(SAMPLE_INPUT).unwrap();
+
+        aoc_common::assert_snapshot!("sample_cave", sim_env.display_string());
+    }
+
+    #[test]
+    fn test_simulation_ticks() {
+        let mut sim_env = parse_simulated_environment(SAMPLE_INPUT).unwrap();
+
+        assert_eq!(sim_env.tick(), Some(true));
+        aoc_common::assert_snapshot!("first_grain_spawned", sim_env.display_string());
+
+        assert_eq!(sim_env.tick(), Some(true));
+        aoc_common::assert_snapshot!("first_grain_falling", sim_env.display_string());
+
+        assert!(sim_env.tick_one_sand());
+        aoc_common::assert_snapshot!("first_grain_resting", sim_env.display_string());
+
+        assert!(sim_env.tick_one_sand());
+        aoc_common::assert_snapshot!("second_grain_resting", sim_env.display_string());
+
+        for _ in 3..=5 {
+            assert!(sim_env.tick_one_sand());
+        }
+
+        aoc_common::assert_snapshot!("five_grains_resting", sim_env.display_string());
+
+        for _ in 6..=22 {
+            assert!(sim_env.tick_one_sand());
+        }
+
+        aoc_common::assert_snapshot!("twenty_two_grains_resting", sim_env.display_string());
+
+        for _ in 23..=24 {
+            assert!(sim_env.tick_one_sand());
+        }
+
+        aoc_common::assert_snapshot!("sample_cave_filled", sim_env.display_string());
+
+        // The next grain falls into the abyss, leaving the cave as it was
+        assert!(!sim_env.tick_one_sand());
+        aoc_common::assert_snapshot!("sample_cave_filled", sim_env.display_string());
+    }
+
+    #[test]
+    fn test_simulation_count() {
+        let mut sim_env = parse_simulated_environment(SAMPLE_INPUT).unwrap();
+        sim_env.tick_till_done().unwrap();
+        assert_eq!(sim_env.count_resting_sand(), 24);
+    }
+
+    #[test]
+    fn test_recorded_frames() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let recorder = Recorder::new(5, sender);
+
+        let mut sim_env = parse_simulated_environment(SAMPLE_INPUT).unwrap();
+        recorder.scope(|| sim_env.tick_till_done()).unwrap();
+
+        let frames: Vec<Frame> = receiver.try_iter().collect();
+        assert!(frames.len() > 24);
+
+        // The first tick spawns a grain just below the spawner
+        let first = &frames[0];
+        assert_eq!((first.left, first.top, first.width, first.height), (494, 0, 10, 10));
+        assert_eq!(first.pixel(6, 1), Tile::Sand(true).color());
+
+        let last = frames.last().unwrap();
+        let resting_sand = (0..last.height)
+            .flat_map(|row| (0..last.width).map(move |column| (column, row)))
+            .filter(|&(column, row)| last.pixel(column, row) == Tile::Sand(false).color())
+            .count();
+        assert_eq!(resting_sand, 24);
+    }
+
+    #[test]
+    fn test_recorded_frames_with_floor() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let recorder = Recorder::new(100, sender);
+
+        let mut sim_env = parse_simulated_environment(SAMPLE_INPUT).unwrap();
+        sim_env.add_floor();
+        recorder.scope(|| sim_env.tick_till_done()).unwrap();
+
+        // Every frame covers the whole pile, even before the sand has spread out that far
+        let frames: Vec<Frame> = receiver.try_iter().collect();
+        assert!(frames.len() > 2);
+        assert!(frames.iter().all(|f| (f.left, f.top, f.width, f.height) == (489, 0, 23, 12)));
+    }
+
+    #[test]
+    fn test_simulation_milestones() {
+        let sim_env = parse_simulated_environment(SAMPLE_INPUT).unwrap();
+
+        for (part, resting_sand) in [(Part::One, 24), (Part::Two, 93)] {
+            let mut simulation = Day14::simulate(&sim_env, part).unwrap();
+            let mut milestones = 0;
+
+            loop {
+                match simulation.step() {
+                    Step::Running => (),
+                    Step::Milestone => milestones += 1,
+                    Step::Finished => break,
+                }
+            }
+
+            // Every grain coming to rest is a milestone, except the last grain of the second
+            // part which fills the spawner
+            assert_eq!(milestones + (part == Part::Two) as usize, resting_sand);
+            assert!(simulation.state().contains(&("resting sand", resting_sand.to_string())));
+        }
+    }
+
+    #[test]
+    fn test_day_2_floor_simulation() {
+        let mut sim_env = parse_simulated_environment(SAMPLE_INPUT).unwrap();
+
+        sim_env.add_floor();
+        sim_env.tick_till_done().unwrap();
+
+        assert_eq!(sim_env.count_resting_sand(), 93);
+        aoc_common::assert_snapshot!("sample_cave_with_floor", sim_env.display_string());
+    }
+
+    #[test]
+    fn test_invalid_rock_paths() {
+        let err = parse_simulated_environment(&b"498,4 -> 498,6\n503,4 -> 502;4\n"[..]).unwrap_err();
+        assert_eq!(err.line_number(), 2);
+        assert_eq!(err.columns(), 10..15);
+
+        let err = parse_simulated_environment(&b"498,4 -> 496,6\n"[..]).unwrap_err();
+        assert_eq!(err.line_number(), 1);
+        assert_eq!(err.columns(), 1..15);
+        assert_eq!(err.message(), "rock paths must be horizontal or vertical");
+
+        let err = parse_simulated_environment(&b"498,4 -> 498,6 -> 5000,6\n"[..]).unwrap_err();
+        assert_eq!(err.columns(), 19..25);
+    }
+
+    #[test]
+    fn test_simulation_params() {
+        // The sample moved 480 to the left along with the spawner, the pile of sand on the floor
+        // is 11 rows deep so it spans 11 columns on either side of the spawner
+        let params = aoc_common::Params::new([
+            ("spawner_x".to_string(), 20.into()),
+            ("width".to_string(), 40.into()),
+            ("height".to_string(), 16.into()),
+        ]);
+
+        let shifted: String = std::str::from_utf8(SAMPLE_INPUT)
+            .unwrap()
+            .replace("49", "1")
+            .replace("50", "2");
+
+        let mut sim_env = params.scope(|| parse_simulated_environment(shifted.as_bytes())).unwrap();
+        sim_env.add_floor();
+        sim_env.tick_till_done().unwrap();
+
+        assert_eq!(sim_env.count_resting_sand(), 93);
+
+        let err = params.scope(|| parse_simulated_environment(SAMPLE_INPUT)).unwrap_err();
+        assert!(err.message().starts_with("point is outside of the simulated area"));
+
+        let params = aoc_common::Params::new([("spawner_y".to_string(), 1023.into())]);
+        let err = params.parse::<Day14Params>().unwrap_err();
+        assert!(err.starts_with("the spawner at 500,1023 is outside of the simulated area"));
+    }
+
+    #[test]
+    fn test_interrupted_simulation() {
+        let mut sim_env = parse_simulated_environment(SAMPLE_INPUT).unwrap();
+
+        let progress = aoc_common::Progress::new().with_timeout(std::time::Duration::ZERO);
+        let err = progress.scope(|| sim_env.tick_till_done()).unwrap_err();
+
+        // The first grain is always allowed to settle before the timeout is noticed
+        assert_eq!(err.progress.map(|p| p.done), Some(1));
+        assert_eq!(sim_env.count_resting_sand(), 1);
+    }
+
+    #[test]
+    fn test_checkpointed_simulation() {
+        let path = std::env::temp_dir().join(format!("day_14-checkpoint-test-{}.json", std::process::id()));
+        let checkpoint = aoc_common::Checkpoint::new(&path, std::time::Duration::from_secs(3600));
+
+        // Interrupt the floor simulation after the first grain, the checkpoint holds that grain
+        let mut sim_env = parse_simulated_environment(SAMPLE_INPUT).unwrap();
+        sim_env.add_floor();
+
+        let progress = aoc_common::Progress::new().with_timeout(std::time::Duration::ZERO);
+        progress.scope(|| checkpoint.scope(|| sim_env.tick_till_done())).unwrap_err();
+
+        let state: SandCheckpoint = checkpoint.load().unwrap();
+        assert_eq!(state, SandCheckpoint { has_floor: true, resting_sand: vec![(500, 8)] });
+
+        // A fresh simulation resumes from the saved sand and finishes with the same count
+        let mut sim_env = parse_simulated_environment(SAMPLE_INPUT).unwrap();
+        sim_env.add_floor();
+
+        let progress = aoc_common::Progress::new();
+        progress.scope(|| checkpoint.scope(|| sim_env.tick_till_done())).unwrap();
+        assert_eq!(sim_env.count_resting_sand(), 93);
+        assert!(!checkpoint.exists());
+
+        // The checkpoint from part two doesn't apply to a simulation without a floor
+        let mut sim_env = parse_simulated_environment(SAMPLE_INPUT).unwrap();
+        checkpoint.save(&state);
+        assert_eq!(sim_env.restore(&state), 0);
+        checkpoint.scope(|| sim_env.tick_till_done()).unwrap();
+        assert_eq!(sim_env.count_resting_sand(), 24);
+    }
+}
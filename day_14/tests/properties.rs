@@ -0,0 +1,57 @@
+use aoc_common::generate::seeded;
+use aoc_common::Solution;
+use day_14::{generate, parse_simulated_environment, Day14};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn test_arbitrary_bytes_never_panic(data in prop::collection::vec(any::<u8>(), 0..256)) {
+        if let Ok(environment) = Day14::parse(&data) {
+            let _ = Day14::part_one(&environment);
+        }
+    }
+
+    #[test]
+    fn test_arbitrary_rock_paths_never_panic(data in "(-?[0-9]{1,5},-?[0-9]{1,5}( -> -?[0-9]{1,5},-?[0-9]{1,5}){0,3}\n){0,4}") {
+        // Part two fills the cave down to the lowest rock, which takes too long to run for every
+        // case when that rock is near the bottom
+        if let Ok(environment) = Day14::parse(data.as_bytes()) {
+            let _ = Day14::part_one(&environment);
+        }
+    }
+}
+
+proptest! {
+    // Every grain counts the sand across the whole cave so fewer cases are run
+    #![proptest_config(ProptestConfig::with_cases(8))]
+
+    #[test]
+    fn test_resting_sand_grows_one_grain_at_a_time(seed in any::<u64>(), paths in 0..30usize) {
+        let input = generate::rock_paths(&mut seeded(seed), paths);
+        let mut sim_env = parse_simulated_environment(input.as_bytes()).unwrap();
+
+        let mut resting_sand = 0;
+
+        while sim_env.tick_one_sand() {
+            resting_sand += 1;
+            prop_assert_eq!(sim_env.count_resting_sand(), resting_sand);
+        }
+    }
+}
+
+proptest! {
+    // Filling the cave up to the spawner takes tens of thousands of grains
+    #![proptest_config(ProptestConfig::with_cases(4))]
+
+    #[test]
+    fn test_floor_holds_more_sand(seed in any::<u64>(), paths in 0..30usize) {
+        let input = generate::rock_paths(&mut seeded(seed), paths);
+        let environment = Day14::parse(input.as_bytes()).unwrap();
+
+        let without_floor: usize = Day14::part_one(&environment).unwrap().parse().unwrap();
+        let with_floor: usize = Day14::part_two(&environment).unwrap().parse().unwrap();
+
+        // Everything that came to rest on the rocks still does with the floor below them
+        prop_assert!(with_floor > without_floor);
+    }
+}
@@ -0,0 +1,29 @@
+use day_14::parse_simulated_environment;
+use geometry::Point;
+
+const SAMPLE_INPUT: &[u8] = include_bytes!("../data/sample");
+
+#[test]
+fn test_sample_simulation() {
+    let mut sim_env = parse_simulated_environment(SAMPLE_INPUT).unwrap();
+
+    // The first grain falls straight down from the spawner, landing on the rocks below it
+    assert_eq!(sim_env.tick(), Some(true));
+    assert_eq!(sim_env.active_sand(), Some(Point::new(500, 1)));
+
+    assert!(sim_env.tick_one_sand());
+    assert_eq!(sim_env.active_sand(), None);
+    assert_eq!(sim_env.count_resting_sand(), 1);
+
+    sim_env.tick_till_done().unwrap();
+    assert_eq!(sim_env.count_resting_sand(), 24);
+}
+
+#[test]
+fn test_sample_simulation_with_floor() {
+    let mut sim_env = parse_simulated_environment(SAMPLE_INPUT).unwrap();
+    sim_env.add_floor();
+
+    sim_env.tick_till_done().unwrap();
+    assert_eq!(sim_env.count_resting_sand(), 93);
+}
@@ -0,0 +1,53 @@
+use aoc_common::generate::Rng;
+
+/// Sensors are placed over the same area as the real reports, with beacons up to this far from
+/// them along either axis.
+const AREA_SIZE: isize = 4_000_000;
+const MAX_BEACON_OFFSET: isize = 1_000_000;
+
+/// A report from `sensors` sensors placed anywhere in the area, each with the closest beacon it
+/// detected.
+pub fn sensor_reports(rng: &mut impl Rng, sensors: usize) -> String {
+    let mut reports = String::new();
+
+    for _ in 0..sensors {
+        let (x, y) = (rng.gen_range(0..=AREA_SIZE), rng.gen_range(0..=AREA_SIZE));
+
+        // A beacon is never in the same place as the sensor that detected it
+        let (beacon_x, beacon_y) = loop {
+            let dx = rng.gen_range(-MAX_BEACON_OFFSET..=MAX_BEACON_OFFSET);
+            let dy = rng.gen_range(-MAX_BEACON_OFFSET..=MAX_BEACON_OFFSET);
+
+            if (dx, dy) != (0, 0) {
+                break (x + dx, y + dy);
+            }
+        };
+
+        reports.push_str(&format!(
+            "Sensor at x={x}, y={y}: closest beacon is at x={beacon_x}, y={beacon_y}\n"
+        ));
+    }
+
+    reports
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::parse_environment;
+    use aoc_common::generate::seeded;
+
+    #[test]
+    fn test_generated_sensor_reports() {
+        let input = sensor_reports(&mut seeded(15), 40);
+        assert_eq!(input, sensor_reports(&mut seeded(15), 40));
+
+        let environment = parse_environment(input.as_bytes()).unwrap();
+        assert_eq!(environment.sensors().len(), 40);
+
+        for sensor in environment.sensors() {
+            assert!((1..=2 * MAX_BEACON_OFFSET as usize).contains(&sensor.beacon_distance()));
+        }
+    }
+}
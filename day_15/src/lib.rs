@@ -0,0 +1,573 @@
+use std::io::BufRead;
+use std::ops::Range;
+
+use aoc_common::diagnostics::parse_lines;
+use aoc_common::generate::InputRng;
+use aoc_common::{Checkpoint, Interrupted, ParseError, Solution};
+use geometry::{Point, Rect};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+pub mod generate;
+
+lazy_static! {
+    static ref LINE_MATCH: Regex =
+        Regex::new(r"Sensor at x=(-?\d+), y=(-?\d+): closest beacon is at x=(-?\d+), y=(-?\d+)")
+            .unwrap();
+}
+
+pub struct Day15;
+
+/// The row to look at for the first part and the area to search for the distress beacon in the
+/// second, which are much smaller for the sample.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Day15Params {
+    pub row: isize,
+    /// Opposite corners of the area to search, as `[x, y]` pairs.
+    pub search_from: (isize, isize),
+    pub search_to: (isize, isize),
+    /// What the beacon's x coordinate is multiplied by for its tuning frequency.
+    pub tuning_multiplier: isize,
+}
+
+impl Default for Day15Params {
+    fn default() -> Self {
+        // I did search the entire space which took about 18 hours hahah, I was going to be away
+        // from my computer anyway so I couldn't spend more time optimizing it... The whole space
+        // is (0, 0) to (4_000_000, 4_000_000).
+        Day15Params {
+            row: 2_000_000,
+            search_from: (3_150_000, 3_360_000),
+            search_to: (3_160_000, 3_370_000),
+            tuning_multiplier: 4_000_000,
+        }
+    }
+}
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    const DATA_DIR: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/data");
+    const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+
+    type Input = Environment;
+    type Params = Day15Params;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        parse_environment(data)
+    }
+
+    fn part_one(input: &Self::Input) -> Option<String> {
+        let params: Day15Params = aoc_common::params::current();
+
+        let detectable_positions = input.detectable_positions_within_row(params.row);
+        Some(detectable_positions.to_string())
+    }
+
+    fn part_two(input: &Self::Input) -> Option<String> {
+        let params: Day15Params = aoc_common::params::current();
+
+        let (from, to) = (params.search_from, params.search_to);
+        let bounds = Rect::new(Point::new(from.0, from.1), Point::new(to.0, to.1));
+
+        input
+            .search_within_bounds(bounds)
+            .ok()?
+            .and_then(|location| {
+                location.x.checked_mul(params.tuning_multiplier)?.checked_add(location.y)
+            })
+            .map(|frequency| frequency.to_string())
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generate::sensor_reports(rng, size))
+    }
+}
+
+#[derive(Debug)]
+pub struct Environment {
+    sensors: Vec<Sensor>,
+}
+
+impl Environment {
+    /// Automatically create a possible bounding box for the entire map's visibility based on the
+    /// sensor's detection range, `None` when there are no sensors.
+    pub fn aabb(&self) -> Option<Rect> {
+        self.sensors
+            .iter()
+            .map(|s| s.detection_aabb())
+            .reduce(|aabb, other| aabb.union(&other))
+    }
+
+    /// Counts the positions in a row where a beacon can't be, as no sensor's nearest beacon is
+    /// there and yet it is within at least one sensor's range.
+    pub fn detectable_positions_within_row(&self, row_coord: isize) -> usize {
+        let mut detectable_positions = 0;
+
+        let columns = self.relevant_row_range(row_coord);
+        tracing::debug!(row = row_coord, ?columns, "counting detectable positions");
+
+        for col_coord in columns {
+            let tgt_coord = Point::new(col_coord, row_coord);
+
+            if self
+                .sensors_within_range_of_row(row_coord)
+                .any(|s| s.detectable_and_empty(tgt_coord))
+            {
+                detectable_positions += 1;
+            }
+        }
+
+        detectable_positions
+    }
+
+    fn new(sensors: Vec<Sensor>) -> Environment {
+        Self { sensors }
+    }
+
+    /// This is not a precise method, it is intended to help scope down the range of the X
+    /// coordinate to limit the number of spaces that need to be checked. This returns the range of
+    /// minimum and maximum X coordinates that any sensor able to see a particular row could
+    /// possibly see.
+    ///
+    /// If the sensor does not exist on the same row as the provided coordinate it, it will not
+    /// actually be able to see the minimum and maximum x coordinate returned.
+    ///
+    /// When no sensor can see the row at all the range is empty.
+    fn relevant_row_range(&self, row_coord: isize) -> Range<isize> {
+        let min_x = self
+            .sensors_within_range_of_row(row_coord)
+            .map(|s| s.min_x_visible())
+            .min();
+        let max_x = self
+            .sensors_within_range_of_row(row_coord)
+            .map(|s| s.max_x_visible())
+            .max();
+
+        match (min_x, max_x) {
+            (Some(min_x), Some(max_x)) => min_x..(max_x + 1),
+            _ => 0..0,
+        }
+    }
+
+    /// Scans the bounds row by row for the one location none of the sensors can detect. This is
+    /// slow over large areas so progress is reported as each row is completed and the search can
+    /// be interrupted through the current progress handle.
+    ///
+    /// When a checkpoint is available the next row to scan is saved periodically and when the
+    /// search is interrupted, a later search over the same bounds picks up from that row.
+    pub fn search_within_bounds(&self, bounds: Rect) -> Result<Option<Point>, Interrupted> {
+        let checkpoint = aoc_common::checkpoint::current();
+        let result = self.search_rows(bounds, checkpoint.as_ref());
+
+        if let Some(checkpoint) = &checkpoint {
+            if result.is_ok() {
+                checkpoint.clear();
+            }
+        }
+
+        result
+    }
+
+    fn search_rows(
+        &self,
+        bounds: Rect,
+        checkpoint: Option<&Checkpoint>,
+    ) -> Result<Option<Point>, Interrupted> {
+        let minimum = bounds.min;
+        let maximum = bounds.max;
+
+        let progress = aoc_common::progress::current();
+        let total_rows = bounds.height() as u64;
+
+        let first_row = checkpoint
+            .and_then(|c| c.load::<SearchCheckpoint>())
+            .filter(|state| state.bounds() == bounds && bounds.rows().contains(&state.next_row))
+            .map_or(minimum.y, |state| state.next_row);
+
+        tracing::debug!(?bounds, first_row, "searching for the distress beacon");
+
+        for search_y in first_row..=maximum.y {
+            let rows_scanned = (search_y - minimum.y) as u64;
+            let state = || SearchCheckpoint::new(bounds, search_y);
+
+            if let Some(checkpoint) = checkpoint {
+                checkpoint.save_if_due(state);
+            }
+
+            if let Err(interrupted) =
+                progress.update("rows scanned", rows_scanned, Some(total_rows))
+            {
+                if let Some(checkpoint) = checkpoint {
+                    checkpoint.save(&state());
+                }
+
+                return Err(interrupted);
+            }
+
+            let relevant_sensors = self.sensors_within_range_of_row(search_y);
+
+            // We know the sensor is within the bounds of a detection range on our row, so
+            // constrain our search to the min / max on the row. If no sensor can see this row at
+            // all then any location on it is undetected.
+            let Some(min_detectable_x) = relevant_sensors.clone().map(|s| s.min_x_visible()).min() else {
+                tracing::debug!(row = search_y, "no sensor can see the row");
+                return Ok(Some(Point::new(minimum.x, search_y)));
+            };
+            let max_detectable_x = relevant_sensors.clone().map(|s| s.max_x_visible()).max().unwrap();
+
+            let min_search_x = minimum.x.max(min_detectable_x);
+            let max_search_x = maximum.x.min(max_detectable_x);
+
+            tracing::trace!(row = search_y, min_search_x, max_search_x, "scanning row");
+
+            for search_x in min_search_x..=max_search_x {
+                let search_loc = Point::new(search_x, search_y);
+
+                if relevant_sensors.clone().any(|s| s.within_detection_range(search_loc)) {
+                    continue;
+                }
+
+                tracing::debug!(x = search_x, y = search_y, "found an undetected location");
+                return Ok(Some(search_loc));
+            }
+        }
+
+        Ok(None)
+    }
+
+    pub fn sensors(&self) -> &[Sensor] {
+        &self.sensors
+    }
+
+    fn sensors_within_range_of_row(
+        &self,
+        row_coord: isize,
+    ) -> impl Clone + Iterator<Item = &Sensor> {
+        self.sensors
+            .iter()
+            .filter(move |s| s.can_detect_row(row_coord))
+    }
+}
+
+/// The saved state of an interrupted [`Environment::search_within_bounds`], the bounds are kept so
+/// a checkpoint is never used for a different search.
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct SearchCheckpoint {
+    min: (isize, isize),
+    max: (isize, isize),
+    next_row: isize,
+}
+
+impl SearchCheckpoint {
+    fn bounds(&self) -> Rect {
+        Rect::new(self.min.into(), self.max.into())
+    }
+
+    fn new(bounds: Rect, next_row: isize) -> Self {
+        Self {
+            min: (bounds.min.x, bounds.min.y),
+            max: (bounds.max.x, bounds.max.y),
+            next_row,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Sensor {
+    location: Point,
+
+    detected_beacon: Point,
+    beacon_distance: usize,
+}
+
+impl Sensor {
+    /// Manhattan distance to the detected beacon, nothing closer than this can be a beacon.
+    pub fn beacon_distance(&self) -> usize {
+        self.beacon_distance
+    }
+
+    /// The absolute minimum y distance between two points is when they are sharing an x
+    /// coordinate. This method is used to find only the sensors that are capable of seeing at
+    /// least one location in a row.
+    fn can_detect_row(&self, row_coord: isize) -> bool {
+        self.within_detection_range(Point::new(self.location.x, row_coord))
+    }
+
+    /// It's not enough to know the whether a position is detectable by a sensor, we also need to
+    /// remove the locations where an existing beacon or sensor is located.
+    fn detectable_and_empty(&self, location: Point) -> bool {
+        self.within_detection_range(location) && !self.known_location(location)
+    }
+
+    /// The area covering everything the sensor could possibly detect. The real detection area is
+    /// a diamond inscribed within this box.
+    pub fn detection_aabb(&self) -> Rect {
+        Rect::around(self.location, self.beacon_distance)
+    }
+
+    pub fn detected_beacon(&self) -> Point {
+        self.detected_beacon
+    }
+
+    fn known_location(&self, location: Point) -> bool {
+        self.location == location || self.detected_beacon == location
+    }
+
+    pub fn location(&self) -> Point {
+        self.location
+    }
+
+    fn max_x_visible(&self) -> isize {
+        self.location.x + self.beacon_distance as isize
+    }
+
+    fn min_x_visible(&self) -> isize {
+        self.location.x - self.beacon_distance as isize
+    }
+
+    fn new(location: Point, detected_beacon: Point) -> Self {
+        let beacon_distance = location.manhattan_distance(detected_beacon);
+
+        Self {
+            location,
+            detected_beacon,
+            beacon_distance,
+        }
+    }
+
+    pub fn within_detection_range(&self, other_location: Point) -> bool {
+        self.location.manhattan_distance(other_location) <= self.beacon_distance
+    }
+}
+
+/// Reads the sensor reports one per line, from memory or streamed from a file. Only the parsed
+/// sensors are kept so the size of the report text doesn't matter.
+pub fn parse_environment<R: BufRead>(reader: R) -> Result<Environment, ParseError> {
+    let sensors: Vec<Sensor> = parse_lines(reader, parse_line)?;
+    Ok(Environment::new(sensors))
+}
+
+fn parse_line(line: &str) -> Result<Sensor, ParseError> {
+    let captures = LINE_MATCH.captures(line).ok_or_else(|| {
+        ParseError::whole_line(
+            line,
+            "expected \"Sensor at x=<x>, y=<y>: closest beacon is at x=<x>, y=<y>\"",
+        )
+    })?;
+
+    // Coordinates are limited to 32 bits so the distances between them can't overflow
+    let coord = |idx: usize| -> Result<isize, ParseError> {
+        let value = captures.get(idx).unwrap().as_str();
+        value
+            .parse::<i32>()
+            .map(|coord| coord as isize)
+            .map_err(|_| ParseError::at(line, value, "coordinate is out of range"))
+    };
+
+    let sensor_loc = Point::new(coord(1)?, coord(2)?);
+    let beacon_loc = Point::new(coord(3)?, coord(4)?);
+
+    Ok(Sensor::new(sensor_loc, beacon_loc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &[u8] = include_bytes!("../data/sample");
+
+    #[test]
+    fn test_environment_with_sample() {
+        let environment = parse_environment(SAMPLE_INPUT).unwrap();
+        assert_eq!(environment.aabb(), Some(Rect::new(Point::new(-8, -10), Point::new(28, 26))));
+        assert_eq!(parse_environment(&b""[..]).unwrap().aabb(), None);
+
+        let relevant_sensor_count = environment.sensors_within_range_of_row(10).count();
+        assert_eq!(relevant_sensor_count, 6);
+
+        let relevant_range = environment.relevant_row_range(10);
+        assert_eq!(relevant_range, -8..29);
+
+        let detectable_positions = environment.detectable_positions_within_row(10);
+        assert_eq!(detectable_positions, 26);
+
+        let bounds = Rect::new(Point::new(0, 0), Point::new(20, 20));
+        let unknown_beacon_position = environment.search_within_bounds(bounds).unwrap().unwrap();
+
+        assert_eq!(unknown_beacon_position, Point::new(14, 11));
+    }
+
+    #[test]
+    fn test_line_parsing() {
+        let data = std::str::from_utf8(SAMPLE_INPUT).unwrap();
+
+        let sensors: Vec<Sensor> = data.lines().map(|l| parse_line(l).unwrap()).collect();
+        assert_eq!(sensors.len(), 14);
+
+        let expected_sensors = vec![
+            Sensor {
+                location: Point::new(2, 18),
+                detected_beacon: Point::new(-2, 15),
+                beacon_distance: 7,
+            },
+            Sensor {
+                location: Point::new(9, 16),
+                detected_beacon: Point::new(10, 16),
+                beacon_distance: 1,
+            },
+            Sensor {
+                location: Point::new(13, 2),
+                detected_beacon: Point::new(15, 3),
+                beacon_distance: 3,
+            },
+            Sensor {
+                location: Point::new(12, 14),
+                detected_beacon: Point::new(10, 16),
+                beacon_distance: 4,
+            },
+            Sensor {
+                location: Point::new(10, 20),
+                detected_beacon: Point::new(10, 16),
+                beacon_distance: 4,
+            },
+            Sensor {
+                location: Point::new(14, 17),
+                detected_beacon: Point::new(10, 16),
+                beacon_distance: 5,
+            },
+            Sensor {
+                location: Point::new(8, 7),
+                detected_beacon: Point::new(2, 10),
+                beacon_distance: 9,
+            },
+            Sensor {
+                location: Point::new(2, 0),
+                detected_beacon: Point::new(2, 10),
+                beacon_distance: 10,
+            },
+            Sensor {
+                location: Point::new(0, 11),
+                detected_beacon: Point::new(2, 10),
+                beacon_distance: 3,
+            },
+            Sensor {
+                location: Point::new(20, 14),
+                detected_beacon: Point::new(25, 17),
+                beacon_distance: 8,
+            },
+            Sensor {
+                location: Point::new(17, 20),
+                detected_beacon: Point::new(21, 22),
+                beacon_distance: 6,
+            },
+            Sensor {
+                location: Point::new(16, 7),
+                detected_beacon: Point::new(15, 3),
+                beacon_distance: 5,
+            },
+            Sensor {
+                location: Point::new(14, 3),
+                detected_beacon: Point::new(15, 3),
+                beacon_distance: 1,
+            },
+            Sensor {
+                location: Point::new(20, 1),
+                detected_beacon: Point::new(15, 3),
+                beacon_distance: 7,
+            },
+        ];
+
+        for (actual, expected) in sensors.iter().zip(expected_sensors) {
+            assert_eq!(actual, &expected);
+        }
+    }
+
+    #[test]
+    fn test_ranging_functions() {
+        let sensor = Sensor::new(Point::new(0, 0), Point::new(5, 5));
+
+        assert!(sensor.can_detect_row(0));
+        assert!(sensor.can_detect_row(10));
+        assert!(sensor.can_detect_row(-10));
+
+        assert!(!sensor.can_detect_row(11));
+        assert!(!sensor.can_detect_row(-11));
+
+        assert_eq!(sensor.min_x_visible(), -10);
+        assert_eq!(sensor.max_x_visible(), 10);
+    }
+
+    #[test]
+    fn test_rows_out_of_sensor_range() {
+        let environment = parse_environment(SAMPLE_INPUT).unwrap();
+
+        assert!(environment.relevant_row_range(2_000_000).is_empty());
+        assert_eq!(environment.detectable_positions_within_row(2_000_000), 0);
+
+        let bounds = Rect::new(Point::new(0, 100), Point::new(20, 120));
+        assert_eq!(environment.search_within_bounds(bounds), Ok(Some(Point::new(0, 100))));
+    }
+
+    #[test]
+    fn test_invalid_report_lines() {
+        let err = parse_environment(&b"Sensor at x=2, y=18: closest beacon is at x=-2, y=15\nSensor at x=9\n"[..])
+            .unwrap_err();
+        assert_eq!(err.line_number(), 2);
+        assert_eq!(err.columns(), 1..14);
+
+        let err = parse_environment(&b"Sensor at x=99999999999999999999, y=18: closest beacon is at x=-2, y=15\n"[..])
+            .unwrap_err();
+        assert_eq!(err.columns(), 13..33);
+
+        let err = parse_environment(&b"Sensor at x=2, y=18: closest beacon is at x=-3000000000, y=15\n"[..])
+            .unwrap_err();
+        assert_eq!(err.message(), "coordinate is out of range");
+    }
+
+    #[test]
+    fn test_interrupted_search() {
+        let environment = parse_environment(SAMPLE_INPUT).unwrap();
+        let bounds = Rect::new(Point::new(0, 0), Point::new(20, 20));
+
+        let progress = aoc_common::Progress::new();
+        progress.cancel();
+
+        let err = progress.scope(|| environment.search_within_bounds(bounds)).unwrap_err();
+        assert_eq!(err.progress.map(|p| (p.done, p.total)), Some((0, Some(21))));
+    }
+
+    #[test]
+    fn test_checkpointed_search() {
+        let environment = parse_environment(SAMPLE_INPUT).unwrap();
+        let bounds = Rect::new(Point::new(0, 0), Point::new(20, 20));
+
+        let path = std::env::temp_dir().join(format!(
+            "day_15-checkpoint-test-{}.json",
+            std::process::id()
+        ));
+        let checkpoint = Checkpoint::new(&path, std::time::Duration::from_secs(3600));
+
+        // An interrupted search records the row it stopped on
+        let progress = aoc_common::Progress::new();
+        progress.cancel();
+        progress
+            .scope(|| checkpoint.scope(|| environment.search_within_bounds(bounds)))
+            .unwrap_err();
+        assert_eq!(checkpoint.load(), Some(SearchCheckpoint::new(bounds, 0)));
+
+        // Resuming past the undetected location proves the saved row is used
+        checkpoint.save(&SearchCheckpoint::new(bounds, 12));
+        let result = checkpoint.scope(|| environment.search_within_bounds(bounds));
+        assert_eq!(result.unwrap(), None);
+        assert!(!checkpoint.exists());
+
+        // Checkpoints from a search over different bounds are ignored
+        let other_bounds = Rect::new(Point::new(0, 0), Point::new(30, 30));
+        checkpoint.save(&SearchCheckpoint::new(other_bounds, 12));
+        let result = checkpoint.scope(|| environment.search_within_bounds(bounds));
+        assert_eq!(result.unwrap(), Some(Point::new(14, 11)));
+        assert!(!checkpoint.exists());
+    }
+}
@@ -0,0 +1,63 @@
+use aoc_common::Solution;
+use day_15::{parse_environment, Day15};
+use geometry::Point;
+use proptest::prelude::*;
+
+fn report_line(sensor: Point, beacon: Point) -> String {
+    format!(
+        "Sensor at x={}, y={}: closest beacon is at x={}, y={}\n",
+        sensor.x, sensor.y, beacon.x, beacon.y
+    )
+}
+
+fn small_point() -> impl Strategy<Value = Point> {
+    (-30..30isize, -30..30isize).prop_map(Point::from)
+}
+
+proptest! {
+    #[test]
+    fn test_arbitrary_bytes_never_panic(data in prop::collection::vec(any::<u8>(), 0..256)) {
+        if let Ok(environment) = Day15::parse(&data) {
+            let _ = Day15::part_one(&environment);
+            let _ = Day15::part_two(&environment);
+        }
+    }
+
+    #[test]
+    fn test_arbitrary_coordinates_never_panic(coords in prop::collection::vec(any::<(i64, i64, i64, i64)>(), 0..4)) {
+        let reports: String = coords
+            .iter()
+            .map(|&(x, y, bx, by)| {
+                report_line(Point::new(x as isize, y as isize), Point::new(bx as isize, by as isize))
+            })
+            .collect();
+
+        if let Ok(environment) = parse_environment(reports.as_bytes()) {
+            let _ = Day15::part_one(&environment);
+            let _ = Day15::part_two(&environment);
+        }
+    }
+
+    #[test]
+    fn test_detectable_positions_match_brute_force(
+        sensors in prop::collection::vec((small_point(), small_point()), 1..6),
+        row in -40..40isize,
+    ) {
+        let reports: String = sensors.iter().map(|&(sensor, beacon)| report_line(sensor, beacon)).collect();
+        let environment = parse_environment(reports.as_bytes()).unwrap();
+
+        // Positions in range of a sensor that aren't that sensor or the beacon it detected
+        let expected = (-200..200)
+            .map(|x| Point::new(x, row))
+            .filter(|&position| {
+                environment.sensors().iter().any(|s| {
+                    s.within_detection_range(position)
+                        && position != s.location()
+                        && position != s.detected_beacon()
+                })
+            })
+            .count();
+
+        prop_assert_eq!(environment.detectable_positions_within_row(row), expected);
+    }
+}
@@ -0,0 +1,49 @@
+use day_15::parse_environment;
+use geometry::{Point, Rect};
+
+const SAMPLE_INPUT: &[u8] = include_bytes!("../data/sample");
+
+#[test]
+fn test_sample_environment() {
+    let environment = parse_environment(SAMPLE_INPUT).unwrap();
+    assert_eq!(environment.sensors().len(), 14);
+
+    let sensor = &environment.sensors()[0];
+    assert_eq!(sensor.location(), Point::new(2, 18));
+    assert_eq!(sensor.detected_beacon(), Point::new(-2, 15));
+    assert_eq!(sensor.beacon_distance(), 7);
+    assert!(sensor.within_detection_range(Point::new(2, 11)));
+    assert!(!sensor.within_detection_range(Point::new(3, 11)));
+    assert!(environment.aabb().unwrap().contains_rect(&sensor.detection_aabb()));
+
+    assert_eq!(environment.detectable_positions_within_row(10), 26);
+
+    let bounds = Rect::new(Point::new(0, 0), Point::new(20, 20));
+    assert_eq!(environment.search_within_bounds(bounds), Ok(Some(Point::new(14, 11))));
+}
+
+#[test]
+fn test_sample_answers_with_params() {
+    use aoc_common::{Params, Solution};
+    use day_15::Day15;
+
+    let params = Params::new([
+        ("row".to_string(), 10.into()),
+        ("search_from".to_string(), vec![0, 0].into()),
+        ("search_to".to_string(), vec![20, 20].into()),
+    ]);
+
+    let environment = Day15::parse(SAMPLE_INPUT).unwrap();
+
+    assert_eq!(params.scope(|| Day15::part_one(&environment)), Some("26".to_string()));
+    assert_eq!(params.scope(|| Day15::part_two(&environment)), Some("56000011".to_string()));
+
+    // A tuning frequency too large to compute has no answer
+    let params = Params::new([
+        ("search_from".to_string(), vec![0, 0].into()),
+        ("search_to".to_string(), vec![20, 20].into()),
+        ("tuning_multiplier".to_string(), (isize::MAX / 10).into()),
+    ]);
+
+    assert_eq!(params.scope(|| Day15::part_two(&environment)), None);
+}
@@ -0,0 +1,43 @@
+use aoc_common::generate::Rng;
+
+use crate::Snafu;
+
+/// Largest number generated, with up to twenty SNAFU digits like the real inputs. Hundreds of
+/// thousands of these can be summed before overflowing.
+const MAX_VALUE: isize = 5_isize.pow(19);
+
+/// A list of `numbers` positive SNAFU numbers.
+pub fn snafu_numbers(rng: &mut impl Rng, numbers: usize) -> String {
+    let mut list = String::new();
+
+    for _ in 0..numbers {
+        // Picking the number of digits first gives short numbers as much of a chance as long ones
+        let max = MAX_VALUE / 5_isize.pow(rng.gen_range(0..19));
+        let number = Snafu::from(rng.gen_range(1..=max));
+
+        list.push_str(&number.snafu());
+        list.push('\n');
+    }
+
+    list
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::parse_input;
+    use aoc_common::generate::seeded;
+
+    #[test]
+    fn test_generated_snafu_numbers() {
+        let input = snafu_numbers(&mut seeded(25), 1000);
+        assert_eq!(input, snafu_numbers(&mut seeded(25), 1000));
+
+        let numbers = parse_input(input.as_bytes()).unwrap();
+        assert_eq!(numbers.len(), 1000);
+        assert!(numbers.iter().all(|n| (1..=MAX_VALUE).contains(&n.base10())));
+        assert!(input.lines().all(|line| line.len() <= 20));
+        assert!(input.lines().any(|line| line.len() < 5));
+    }
+}
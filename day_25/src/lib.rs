@@ -0,0 +1,188 @@
+use std::io::BufRead;
+
+use aoc_common::diagnostics::parse_lines;
+use aoc_common::generate::InputRng;
+use aoc_common::{NoParams, ParseError, Solution};
+
+pub mod generate;
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+    const DATA_DIR: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/data");
+    const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+
+    type Input = Vec<Snafu>;
+    type Params = NoParams;
+
+    fn parse(data: &[u8]) -> Result<Self::Input, ParseError> {
+        parse_input(data)
+    }
+
+    fn part_one(input: &Self::Input) -> Option<String> {
+        let result: Snafu = input.iter().sum::<Option<Snafu>>()?;
+        tracing::debug!(numbers = input.len(), total = result.base10(), "summed fuel requirements");
+
+        Some(result.snafu())
+    }
+
+    /// The last day only has a single puzzle
+    fn part_two(_input: &Self::Input) -> Option<String> {
+        None
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generate::snafu_numbers(rng, size))
+    }
+}
+
+#[derive(Debug)]
+pub struct Snafu {
+    base_10_value: isize,
+}
+
+impl Snafu {
+    pub fn base10(&self) -> isize {
+        self.base_10_value
+    }
+
+    /// The sum of both numbers, `None` when it doesn't fit.
+    pub fn checked_add(&self, other: &Snafu) -> Option<Snafu> {
+        let base_10_value = self.base_10_value.checked_add(other.base_10_value)?;
+
+        Some(Snafu { base_10_value })
+    }
+
+    pub fn snafu(&self) -> String {
+        if self.base_10_value == 0 {
+            return "0".to_string();
+        }
+
+        let mut digits = vec![];
+        // Widened so shifting the remainder can't overflow at the ends of the range
+        let mut remaining = self.base_10_value as i128;
+
+        while remaining != 0 {
+            // Each place can hold a value from -2 to 2, shift the remainder into that window and
+            // carry anything left over into the next place.
+            let place_value = (remaining + 2).rem_euclid(5) - 2;
+
+            digits.push(match place_value {
+                -2 => '=',
+                -1 => '-',
+                0 => '0',
+                1 => '1',
+                2 => '2',
+                _ => unreachable!(),
+            });
+
+            remaining = (remaining - place_value) / 5;
+        }
+
+        digits.into_iter().rev().collect()
+    }
+}
+
+impl From<isize> for Snafu {
+    fn from(base_10_value: isize) -> Self {
+        Snafu { base_10_value }
+    }
+}
+
+impl std::str::FromStr for Snafu {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_line(s)
+    }
+}
+
+/// Sums the numbers, `None` when the total doesn't fit.
+impl<'a> std::iter::Sum<&'a Snafu> for Option<Snafu> {
+    fn sum<I: Iterator<Item = &'a Snafu>>(mut iter: I) -> Option<Snafu> {
+        iter.try_fold(Snafu { base_10_value: 0 }, |acc, sn| acc.checked_add(sn))
+    }
+}
+
+pub fn parse_input<R: BufRead>(reader: R) -> Result<Vec<Snafu>, ParseError> {
+    parse_lines(reader, parse_line)
+}
+
+fn parse_line(line: &str) -> Result<Snafu, ParseError> {
+    let number = line.trim();
+    if number.is_empty() {
+        return Err(ParseError::whole_line(line, "expected a SNAFU number"));
+    }
+
+    let mut base_10_value: isize = 0;
+
+    for (idx, digit) in number.char_indices() {
+        let digit_value = match digit {
+            '2' => 2,
+            '1' => 1,
+            '0' => 0,
+            '-' => -1,
+            '=' => -2,
+            _ => {
+                let digit_str = &number[idx..idx + digit.len_utf8()];
+                let msg = format!("invalid SNAFU digit {:?}, expected one of 2, 1, 0, - or =", digit);
+
+                return Err(ParseError::at(line, digit_str, msg));
+            }
+        };
+
+        base_10_value = base_10_value
+            .checked_mul(5)
+            .and_then(|v| v.checked_add(digit_value))
+            .ok_or_else(|| ParseError::at(line, number, "SNAFU number is too large"))?;
+    }
+
+    tracing::trace!(number, base_10_value, "converted SNAFU number");
+
+    Ok(Snafu { base_10_value })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &[u8] = include_bytes!("../data/sample");
+
+    #[test]
+    fn test_sample_input() {
+        let sample_nums = parse_input(SAMPLE_INPUT).unwrap();
+
+        let base_10s: Vec<isize> = sample_nums.iter().map(|sn| sn.base10()).collect();
+        assert_eq!(base_10s, vec![1747, 906, 198, 11, 201, 31, 1257, 32, 353, 107, 7, 3, 37]);
+
+        let snafu_sum: Snafu = sample_nums.iter().sum::<Option<Snafu>>().unwrap();
+        assert_eq!(snafu_sum.base10(), 4890);
+        assert_eq!(snafu_sum.snafu(), "2=-1=0".to_string());
+    }
+
+    #[test]
+    fn test_invalid_snafu_digits() {
+        let err = parse_input(&b"1=-0-2\n12131\n"[..]).unwrap_err();
+        assert_eq!(err.line_number(), 2);
+        assert_eq!(err.columns(), 4..5);
+
+        let err = parse_input(&b"2222222222222222222222222222222\n"[..]).unwrap_err();
+        assert_eq!(err.message(), "SNAFU number is too large");
+    }
+
+    #[test]
+    fn test_overflowing_sum() {
+        let large = "2".repeat(27);
+        let numbers = parse_input(format!("{large}\n{large}\n{large}\n").as_bytes()).unwrap();
+
+        assert_eq!(Day25::part_one(&numbers), None);
+
+        let numbers = parse_input(format!("{large}\n").as_bytes()).unwrap();
+        assert_eq!(Day25::part_one(&numbers), Some(large));
+
+        // Numbers at the ends of the range still convert
+        assert_eq!(Snafu::from(isize::MAX).snafu(), "1110--=-02=100==0-0=11=11212");
+        assert_eq!(Snafu::from(isize::MIN).snafu(), "---011210=2-00220102--2--==2");
+    }
+}
@@ -0,0 +1,43 @@
+use aoc_common::generate::seeded;
+use aoc_common::Solution;
+use day_25::{generate, parse_input, Day25, Snafu};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn test_arbitrary_bytes_never_panic(data in prop::collection::vec(any::<u8>(), 0..256)) {
+        if let Ok(numbers) = Day25::parse(&data) {
+            let _ = Day25::part_one(&numbers);
+        }
+    }
+
+    #[test]
+    fn test_arbitrary_digits_never_panic(numbers in "([210=-]{1,30}\n){1,4}") {
+        if let Ok(numbers) = Day25::parse(numbers.as_bytes()) {
+            let _ = Day25::part_one(&numbers);
+        }
+    }
+
+    #[test]
+    fn test_snafu_round_trip(value in -(1_isize << 60)..(1_isize << 60)) {
+        let snafu = Snafu::from(value).snafu();
+        let parsed: Snafu = snafu.parse().unwrap();
+
+        prop_assert_eq!(parsed.base10(), value);
+    }
+
+    #[test]
+    fn test_digits_round_trip(number in "[21=-][210=-]{0,20}") {
+        let parsed: Snafu = number.parse().unwrap();
+        prop_assert_eq!(parsed.snafu(), number);
+    }
+
+    #[test]
+    fn test_sum_matches_base_10_sum(seed in any::<u64>(), numbers in 0..200usize) {
+        let input = generate::snafu_numbers(&mut seeded(seed), numbers);
+        let numbers = parse_input(input.as_bytes()).unwrap();
+
+        let sum: Snafu = numbers.iter().sum::<Option<Snafu>>().unwrap();
+        prop_assert_eq!(sum.base10(), numbers.iter().map(|n| n.base10()).sum::<isize>());
+    }
+}
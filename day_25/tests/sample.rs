@@ -0,0 +1,21 @@
+use day_25::{parse_input, Snafu};
+
+const SAMPLE_INPUT: &[u8] = include_bytes!("../data/sample");
+
+#[test]
+fn test_sample_sum() {
+    let numbers = parse_input(SAMPLE_INPUT).unwrap();
+
+    let sum: Snafu = numbers.iter().sum::<Option<Snafu>>().unwrap();
+    assert_eq!(sum.snafu(), "2=-1=0");
+}
+
+#[test]
+fn test_conversions() {
+    let number: Snafu = "1121-1110-1=0".parse().unwrap();
+    assert_eq!(number.base10(), 314159265);
+    assert_eq!(Snafu::from(314159265).snafu(), "1121-1110-1=0");
+
+    let err = "12a".parse::<Snafu>().unwrap_err();
+    assert_eq!(err.columns(), 3..4);
+}
@@ -0,0 +1,284 @@
+use std::ops::{Index, IndexMut};
+
+use crate::{Point, Rect};
+
+const ORTHOGONAL_OFFSETS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+const ALL_OFFSETS: [(isize, isize); 8] = [
+    (-1, -1), (0, -1), (1, -1),
+    (-1, 0), (1, 0),
+    (-1, 1), (0, 1), (1, 1),
+];
+
+/// Which cells count as the neighbors of a cell.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Adjacency {
+    /// The four cells sharing an edge with the cell.
+    Orthogonal,
+    /// The eight cells sharing an edge or a corner with the cell.
+    All,
+}
+
+impl Adjacency {
+    fn offsets(&self) -> &'static [(isize, isize)] {
+        match self {
+            Adjacency::Orthogonal => &ORTHOGONAL_OFFSETS,
+            Adjacency::All => &ALL_OFFSETS,
+        }
+    }
+}
+
+/// A rectangular grid of cells addressed by [`Point`], with the top left cell at the origin. The
+/// cells are stored row by row.
+///
+/// Every access is bounds checked, [`Grid::get`] returns `None` for points outside of the grid
+/// and indexing outside of it panics rather than wrapping around onto another row.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// The area covered by the grid.
+    pub fn bounds(&self) -> Rect {
+        Rect::new(Point::ORIGIN, Point::new(self.width as isize - 1, self.height as isize - 1))
+    }
+
+    /// Every cell, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// The cells of a single column from top to bottom, `None` if the column is outside of the
+    /// grid.
+    pub fn column(&self, x: usize) -> Option<impl Iterator<Item = &T>> {
+        (x < self.width).then(|| self.cells.iter().skip(x).step_by(self.width))
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.index_of(point).is_some()
+    }
+
+    /// Creates a grid by calling `cell` with the point of every cell, row by row.
+    ///
+    /// Panics if the grid would be empty.
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut(Point) -> T) -> Self {
+        assert!(width > 0 && height > 0, "a grid needs at least one row and column");
+
+        let cells = (0..height as isize)
+            .flat_map(|y| (0..width as isize).map(move |x| Point::new(x, y)))
+            .map(&mut cell)
+            .collect();
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index_of(point).map(|idx| &mut self.cells[idx])
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index_of(&self, point: Point) -> Option<usize> {
+        let x = usize::try_from(point.x).ok().filter(|&x| x < self.width)?;
+        let y = usize::try_from(point.y).ok().filter(|&y| y < self.height)?;
+
+        Some(x + y * self.width)
+    }
+
+    /// Every cell along with its point, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        let width = self.width;
+
+        self.cells.iter().enumerate().map(move |(idx, cell)| {
+            (Point::new((idx % width) as isize, (idx / width) as isize), cell)
+        })
+    }
+
+    /// The neighbors of the point that are within the grid, clockwise from the one above it.
+    /// Diagonal neighbors are listed row by row instead.
+    pub fn neighbors(
+        &self,
+        point: Point,
+        adjacency: Adjacency,
+    ) -> impl Iterator<Item = (Point, &T)> {
+        adjacency.offsets().iter().filter_map(move |&(dx, dy)| {
+            let neighbor = point.offset(dx, dy);
+            self.get(neighbor).map(|cell| (neighbor, cell))
+        })
+    }
+
+    /// Copies the cells within the rectangle into a new grid, with the rectangle's top left corner
+    /// as its origin. `None` if the rectangle isn't entirely within the grid.
+    pub fn region(&self, rect: Rect) -> Option<Grid<T>>
+    where
+        T: Clone,
+    {
+        if !self.bounds().contains_rect(&rect) {
+            return None;
+        }
+
+        Some(Grid::from_fn(rect.width(), rect.height(), |point| {
+            self[point + rect.min].clone()
+        }))
+    }
+
+    /// Draws the grid a row per line, using `cell` to pick the character for each cell.
+    pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&mut cell).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The cells of a single row from left to right, `None` if the row is outside of the grid.
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> Option<&mut [T]> {
+        (y < self.height).then(|| &mut self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    /// Replaces the cell at the point, returning the previous value. Returns `None` and leaves the
+    /// grid as it was when the point is outside of the grid.
+    pub fn set(&mut self, point: Point, value: T) -> Option<T> {
+        self.get_mut(point).map(|cell| std::mem::replace(cell, value))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid with every cell set to `value`.
+    ///
+    /// Panics if the grid would be empty.
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        assert!(width > 0 && height > 0, "a grid needs at least one row and column");
+
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        match self.index_of(point) {
+            Some(idx) => &self.cells[idx],
+            None => panic!("{point:?} is outside of the {}x{} grid", self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        match self.index_of(point) {
+            Some(idx) => &mut self.cells[idx],
+            None => panic!("{point:?} is outside of the {}x{} grid", self.width, self.height),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbered() -> Grid<usize> {
+        Grid::from_fn(4, 3, |point| point.x as usize + point.y as usize * 10)
+    }
+
+    #[test]
+    fn test_checked_access() {
+        let mut grid = numbered();
+
+        assert_eq!(grid.get(Point::new(3, 2)), Some(&23));
+        assert_eq!(grid.get(Point::new(4, 0)), None);
+        assert_eq!(grid.get(Point::new(-1, 1)), None);
+        assert_eq!(grid.get(Point::new(0, 3)), None);
+
+        assert_eq!(grid.set(Point::new(1, 1), 99), Some(11));
+        assert_eq!(grid.set(Point::new(1, 5), 99), None);
+        assert_eq!(grid[Point::new(1, 1)], 99);
+
+        grid[Point::new(0, 0)] = 7;
+        assert_eq!(grid.row(0), Some(&[7, 1, 2, 3][..]));
+        assert_eq!(grid.bounds(), Rect::new(Point::ORIGIN, Point::new(3, 2)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Point { x: 4, y: 0 } is outside of the 4x3 grid")]
+    fn test_index_out_of_bounds() {
+        // Would have been the first cell of the second row if the index wrapped around
+        let _ = numbered()[Point::new(4, 0)];
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = numbered();
+
+        let orthogonal: Vec<usize> = grid
+            .neighbors(Point::new(1, 1), Adjacency::Orthogonal)
+            .map(|(_, &cell)| cell)
+            .collect();
+        assert_eq!(orthogonal, vec![1, 12, 21, 10]);
+
+        let corner: Vec<Point> = grid
+            .neighbors(Point::ORIGIN, Adjacency::All)
+            .map(|(point, _)| point)
+            .collect();
+        assert_eq!(corner, vec![Point::new(1, 0), Point::new(0, 1), Point::new(1, 1)]);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let mut grid = numbered();
+
+        assert_eq!(grid.rows().count(), 3);
+        assert_eq!(grid.row(3), None);
+        assert_eq!(grid.column(2).unwrap().copied().collect::<Vec<_>>(), vec![2, 12, 22]);
+        assert!(grid.column(4).is_none());
+
+        grid.row_mut(2).unwrap().fill(0);
+        assert_eq!(grid.cells().sum::<usize>(), 52);
+
+        let points: Vec<Point> = grid.iter().filter(|(_, &c)| c == 11).map(|(p, _)| p).collect();
+        assert_eq!(points, vec![Point::new(1, 1)]);
+    }
+
+    #[test]
+    fn test_region_and_render() {
+        let grid = numbered();
+
+        let region = grid.region(Rect::new(Point::new(1, 1), Point::new(3, 2))).unwrap();
+        assert_eq!((region.width(), region.height()), (3, 2));
+        assert_eq!(region[Point::ORIGIN], 11);
+
+        assert_eq!(grid.region(Rect::new(Point::new(1, 1), Point::new(4, 2))), None);
+
+        let even = Grid::from_fn(3, 2, |point| (point.x + point.y) % 2 == 0);
+        assert_eq!(even.render(|&e| if e { '#' } else { '.' }), "#.#\n.#.");
+    }
+}
@@ -0,0 +1,12 @@
+//! Integer 2D geometry shared by the grid based puzzles. Coordinates follow the puzzles' own
+//! convention where y grows downwards.
+
+mod grid;
+mod line;
+mod point;
+mod rect;
+
+pub use grid::{Adjacency, Grid};
+pub use line::LineSegment;
+pub use point::{abs_distance, Point};
+pub use rect::Rect;
@@ -0,0 +1,107 @@
+use crate::{Point, Rect};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LineSegment {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl LineSegment {
+    pub fn aabb(&self) -> Rect {
+        Rect::new(self.start, self.end)
+    }
+
+    /// Whether the segment runs at exactly 45 degrees.
+    pub fn diagonal(&self) -> bool {
+        let delta = self.end - self.start;
+        delta.x != 0 && delta.x.abs() == delta.y.abs()
+    }
+
+    pub fn horizontal(&self) -> bool {
+        self.start.x != self.end.x && self.start.y == self.end.y
+    }
+
+    pub fn new(start: Point, end: Point) -> Self {
+        LineSegment { start, end }
+    }
+
+    /// Rasterizes the segment into every point it passes through, from the start to the end
+    /// inclusive. Lines that aren't horizontal, vertical or diagonal are approximated using
+    /// Bresenham's algorithm.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let delta_x = self.start.x.abs_diff(self.end.x) as isize;
+        let delta_y = -(self.start.y.abs_diff(self.end.y) as isize);
+
+        let step_x = (self.end.x - self.start.x).signum();
+        let step_y = (self.end.y - self.start.y).signum();
+
+        let end = self.end;
+        let mut next = Some(self.start);
+        let mut error = delta_x + delta_y;
+
+        std::iter::from_fn(move || {
+            let current = next?;
+
+            next = if current == end {
+                None
+            } else {
+                let mut point = current;
+                let doubled_error = error * 2;
+
+                if doubled_error >= delta_y {
+                    error += delta_y;
+                    point.x += step_x;
+                }
+
+                if doubled_error <= delta_x {
+                    error += delta_x;
+                    point.y += step_y;
+                }
+
+                Some(point)
+            };
+
+            Some(current)
+        })
+    }
+
+    pub fn vertical(&self) -> bool {
+        self.start.x == self.end.x && self.start.y != self.end.y
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(start: (isize, isize), end: (isize, isize)) -> Vec<(isize, isize)> {
+        LineSegment::new(start.into(), end.into())
+            .points()
+            .map(|p| (p.x, p.y))
+            .collect()
+    }
+
+    #[test]
+    fn test_orientation() {
+        let line = LineSegment::new(Point::new(498, 4), Point::new(498, 6));
+        assert!(line.vertical());
+        assert!(!line.horizontal());
+
+        let line = LineSegment::new(Point::new(498, 6), Point::new(496, 6));
+        assert!(line.horizontal());
+        assert!(!line.diagonal());
+
+        let line = LineSegment::new(Point::new(1, 1), Point::new(3, 3));
+        assert!(line.diagonal());
+        assert!(!line.horizontal() && !line.vertical());
+    }
+
+    #[test]
+    fn test_rasterization() {
+        assert_eq!(points((498, 4), (498, 6)), vec![(498, 4), (498, 5), (498, 6)]);
+        assert_eq!(points((498, 6), (496, 6)), vec![(498, 6), (497, 6), (496, 6)]);
+        assert_eq!(points((3, 3), (1, 1)), vec![(3, 3), (2, 2), (1, 1)]);
+        assert_eq!(points((5, 5), (5, 5)), vec![(5, 5)]);
+        assert_eq!(points((0, 0), (4, 2)), vec![(0, 0), (1, 1), (2, 1), (3, 2), (4, 2)]);
+    }
+}
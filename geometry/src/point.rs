@@ -0,0 +1,105 @@
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    /// The largest distance along either axis, the number of moves between the two points when
+    /// diagonal moves are allowed.
+    pub fn chebyshev_distance(&self, other: Point) -> usize {
+        abs_distance(self.x, other.x).max(abs_distance(self.y, other.y))
+    }
+
+    /// The distance between the two points when only moving along the axes.
+    pub fn manhattan_distance(&self, other: Point) -> usize {
+        abs_distance(self.x, other.x) + abs_distance(self.y, other.y)
+    }
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Point { x, y }
+    }
+
+    pub fn offset(&self, dx: isize, dy: isize) -> Point {
+        Point::new(self.x + dx, self.y + dy)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl From<(isize, isize)> for Point {
+    fn from((x, y): (isize, isize)) -> Point {
+        Point::new(x, y)
+    }
+}
+
+/// Parses the common `x,y` notation used by a number of puzzle inputs.
+impl FromStr for Point {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Point, String> {
+        let str_coords: Vec<&str> = value.split(',').collect();
+
+        match str_coords[..] {
+            [x, y] => {
+                let x = x.parse::<isize>().map_err(|_| format!("invalid x coordinate {:?}", x))?;
+                let y = y.parse::<isize>().map_err(|_| format!("invalid y coordinate {:?}", y))?;
+
+                Ok(Point::new(x, y))
+            }
+            _ => Err(format!("expected a point formatted as x,y but found {:?}", value)),
+        }
+    }
+}
+
+pub fn abs_distance(left: isize, right: isize) -> usize {
+    (left - right).unsigned_abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ranging_functions() {
+        assert_eq!(abs_distance(0, 5), 5);
+        assert_eq!(abs_distance(-20, -20), 0);
+        assert_eq!(abs_distance(-10, 10), 20);
+
+        assert_eq!(Point::ORIGIN.manhattan_distance(Point::ORIGIN), 0);
+        assert_eq!(Point::new(-10, 0).manhattan_distance(Point::new(0, 10)), 20);
+        assert_eq!(Point::new(0, 5).manhattan_distance(Point::new(5, 5)), 5);
+
+        assert_eq!(Point::new(-10, 0).chebyshev_distance(Point::new(0, 10)), 10);
+        assert_eq!(Point::new(3, 1).chebyshev_distance(Point::new(4, 7)), 6);
+    }
+
+    #[test]
+    fn test_parsing() {
+        assert_eq!("498,4".parse::<Point>(), Ok(Point::new(498, 4)));
+        assert_eq!("-2,15".parse::<Point>(), Ok(Point::new(-2, 15)));
+
+        assert!("498".parse::<Point>().is_err());
+        assert!("498,4,1".parse::<Point>().is_err());
+        assert!("498,y".parse::<Point>().is_err());
+    }
+}
@@ -0,0 +1,161 @@
+use std::ops::RangeInclusive;
+
+use crate::Point;
+
+/// An axis aligned bounding box. Both corners are inclusive so a rectangle always covers at least
+/// one point.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Rect {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Rect {
+    /// The smallest rectangle covering every provided point, `None` if there aren't any.
+    pub fn bounding(points: impl IntoIterator<Item = Point>) -> Option<Rect> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+
+        Some(points.fold(Rect::from_point(first), |rect, p| rect.include(p)))
+    }
+
+    pub fn columns(&self) -> RangeInclusive<isize> {
+        self.min.x..=self.max.x
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.columns().contains(&point.x) && self.rows().contains(&point.y)
+    }
+
+    pub fn contains_rect(&self, other: &Rect) -> bool {
+        self.contains(other.min) && self.contains(other.max)
+    }
+
+    pub fn from_point(point: Point) -> Rect {
+        Rect {
+            min: point,
+            max: point,
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.max.y.abs_diff(self.min.y) + 1
+    }
+
+    /// Grows the rectangle just enough to cover the provided point.
+    pub fn include(&self, point: Point) -> Rect {
+        Rect {
+            min: Point::new(self.min.x.min(point.x), self.min.y.min(point.y)),
+            max: Point::new(self.max.x.max(point.x), self.max.y.max(point.y)),
+        }
+    }
+
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let min = Point::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y));
+        let max = Point::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y));
+
+        if min.x > max.x || min.y > max.y {
+            return None;
+        }
+
+        Some(Rect { min, max })
+    }
+
+    /// Creates the rectangle spanning the two corners, in any order.
+    pub fn new(corner: Point, opposite: Point) -> Rect {
+        Rect::from_point(corner).include(opposite)
+    }
+
+    /// Every point within the rectangle, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let columns = self.columns();
+        self.rows()
+            .flat_map(move |y| columns.clone().map(move |x| Point::new(x, y)))
+    }
+
+    pub fn rows(&self) -> RangeInclusive<isize> {
+        self.min.y..=self.max.y
+    }
+
+    pub fn union(&self, other: &Rect) -> Rect {
+        self.include(other.min).include(other.max)
+    }
+
+    pub fn width(&self) -> usize {
+        self.max.x.abs_diff(self.min.x) + 1
+    }
+
+    /// The square covering everything within `radius` of the center along each axis.
+    pub fn around(center: Point, radius: usize) -> Rect {
+        let radius = radius as isize;
+
+        Rect {
+            min: center.offset(-radius, -radius),
+            max: center.offset(radius, radius),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_construction() {
+        let rect = Rect::new(Point::new(502, 9), Point::new(494, 4));
+
+        assert_eq!(rect.min, Point::new(494, 4));
+        assert_eq!(rect.max, Point::new(502, 9));
+        assert_eq!(rect.width(), 9);
+        assert_eq!(rect.height(), 6);
+
+        let bounds = Rect::bounding([Point::new(3, -1), Point::new(-2, 4), Point::new(0, 0)]);
+        assert_eq!(bounds, Some(Rect::new(Point::new(-2, -1), Point::new(3, 4))));
+        assert_eq!(Rect::bounding([]), None);
+
+        assert_eq!(
+            Rect::around(Point::new(8, 7), 9),
+            Rect::new(Point::new(-1, -2), Point::new(17, 16))
+        );
+    }
+
+    #[test]
+    fn test_containment() {
+        let rect = Rect::new(Point::new(0, 0), Point::new(20, 20));
+
+        assert!(rect.contains(Point::new(0, 0)));
+        assert!(rect.contains(Point::new(20, 20)));
+        assert!(rect.contains(Point::new(14, 11)));
+        assert!(!rect.contains(Point::new(21, 11)));
+        assert!(!rect.contains(Point::new(14, -1)));
+
+        assert!(rect.contains_rect(&Rect::new(Point::new(1, 1), Point::new(5, 5))));
+        assert!(!rect.contains_rect(&Rect::new(Point::new(1, 1), Point::new(25, 5))));
+    }
+
+    #[test]
+    fn test_union_and_intersection() {
+        let left = Rect::new(Point::new(0, 0), Point::new(5, 5));
+        let right = Rect::new(Point::new(3, 4), Point::new(8, 10));
+
+        assert_eq!(left.union(&right), Rect::new(Point::new(0, 0), Point::new(8, 10)));
+        assert_eq!(
+            left.intersection(&right),
+            Some(Rect::new(Point::new(3, 4), Point::new(5, 5)))
+        );
+
+        let far = Rect::new(Point::new(6, 6), Point::new(7, 7));
+        assert_eq!(left.intersection(&far), None);
+    }
+
+    #[test]
+    fn test_points() {
+        let rect = Rect::new(Point::new(1, 1), Point::new(2, 2));
+        let points: Vec<Point> = rect.points().collect();
+
+        assert_eq!(
+            points,
+            vec![Point::new(1, 1), Point::new(2, 1), Point::new(1, 2), Point::new(2, 2)]
+        );
+    }
+}